@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use std::mem::MaybeUninit;
+
+use placement_new::{overwrite, uninit_project, CloneInto, SinglePlace, UninitProject};
+
+#[derive(UninitProject, CloneInto)]
+#[repr(C)]
+pub struct Snapshot {
+    version: u64,
+    name: String,
+    #[placement(clone)]
+    index: HashMap<String, usize>,
+    pages: [[u8; 4096]; 16],
+}
+
+#[derive(Debug, PartialEq, UninitProject, CloneInto)]
+#[repr(C)]
+pub enum Shape {
+    Empty,
+    Circle(f64),
+    Polygon {
+        name: String,
+        points: Vec<(i32, i32)>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, CloneInto)]
+#[placement(copy)]
+pub struct Extent<T> {
+    pub offset: T,
+    pub len: T,
+}
+
+#[derive(UninitProject, CloneInto)]
+#[repr(C)]
+pub struct Layout {
+    #[placement(copy)]
+    pub extents: [Extent<u32>; 64],
+    pub root: Extent<u64>,
+}
+
+#[derive(UninitProject, CloneInto)]
+#[repr(C)]
+pub struct Versioned<T, const N: usize> {
+    pub version: u64,
+    pub values: [T; N],
+    #[placement(clone)]
+    pub history: Vec<T>,
+}
+
+impl Snapshot {
+    #[must_use]
+    pub fn new_boxed(name: &str) -> Box<Self> {
+        unsafe {
+            Box::emplace_zeroed_with(|this: &mut MaybeUninit<Self>| {
+                let this = uninit_project!(this);
+                overwrite(&mut this.version, 1);
+                overwrite(&mut this.name, String::from(name));
+                overwrite(&mut this.index, HashMap::new());
+            })
+        }
+    }
+
+    #[must_use]
+    pub fn duplicate(&self) -> Box<Self> {
+        Box::emplace_clone(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Extent, Layout, Shape, Snapshot, Versioned};
+
+    use std::cell::Cell;
+    use std::mem::MaybeUninit;
+    use std::panic;
+
+    use placement_new::{overwrite, CloneInto, SinglePlace, SlicePlace};
+
+    #[test]
+    fn check_duplicate() {
+        let mut s = Snapshot::new_boxed("first");
+        s.index.insert(String::from("a"), 1);
+        s.pages[3][7] = 42;
+
+        let t = s.duplicate();
+        assert_eq!(t.version, 1);
+        assert_eq!(t.name, "first");
+        assert_eq!(t.index.get("a"), Some(&1));
+        assert_eq!(t.pages[3][7], 42);
+    }
+
+    #[test]
+    fn check_enum() {
+        let shapes = [
+            Shape::Empty,
+            Shape::Circle(1.5),
+            Shape::Polygon {
+                name: String::from("triangle"),
+                points: vec![(0, 0), (1, 0), (0, 1)],
+            },
+        ];
+        let cloned: Box<[Shape]> = Box::emplace_clone_slice(&shapes);
+        assert_eq!(&*cloned, &shapes);
+    }
+
+    #[test]
+    fn check_generic() {
+        let v = Versioned {
+            version: 2,
+            values: [String::from("a"), String::from("b")],
+            history: vec![String::from("c")],
+        };
+        let w = Box::emplace_clone(&v);
+        assert_eq!(w.version, 2);
+        assert_eq!(w.values, v.values);
+        assert_eq!(w.history, v.history);
+    }
+
+    #[test]
+    fn check_copy() {
+        let mut layout = Layout {
+            extents: [Extent { offset: 0, len: 0 }; 64],
+            root: Extent { offset: 1, len: 2 },
+        };
+        layout.extents[5] = Extent {
+            offset: 10,
+            len: 20,
+        };
+        let cloned = Box::emplace_clone(&layout);
+        assert_eq!(cloned.extents, layout.extents);
+        assert_eq!(cloned.root, Extent { offset: 1, len: 2 });
+
+        let mut dst = MaybeUninit::uninit();
+        placement_new::copy_into(&layout.root, &mut dst);
+        assert_eq!(unsafe { dst.assume_init() }, layout.root);
+    }
+
+    thread_local! {
+        static DROP_COUNT: Cell<usize> = const { Cell::new(0) };
+    }
+
+    struct Fragile(usize);
+
+    unsafe impl CloneInto for Fragile {
+        fn clone_into_uninit(&self, dst: &mut MaybeUninit<Self>) {
+            assert!(self.0 != 3);
+            overwrite(dst, Self(self.0));
+        }
+    }
+
+    impl Drop for Fragile {
+        fn drop(&mut self) {
+            DROP_COUNT.with(|c| c.set(c.get() + 1));
+        }
+    }
+
+    #[test]
+    fn check_unwind() {
+        let src: Vec<Fragile> = (0..8).map(Fragile).collect();
+        let ret = panic::catch_unwind(|| {
+            let _: Box<[Fragile]> = Box::emplace_clone_slice(&src);
+        });
+        assert!(ret.is_err());
+        assert_eq!(DROP_COUNT.with(Cell::get), 3);
+    }
+}
@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+
+use placement_new::{DefaultInPlace, SinglePlace, UninitProject};
+
+#[derive(UninitProject, DefaultInPlace)]
+#[repr(C)]
+pub struct Config {
+    retries: u32,
+    #[placement(default = String::from("localhost"))]
+    host: String,
+}
+
+#[derive(UninitProject, DefaultInPlace)]
+#[repr(C)]
+pub struct State {
+    config: Config,
+    #[placement(default = 64)]
+    capacity: usize,
+    names: HashMap<String, u64>,
+    slots: [Option<Box<u64>>; 1024],
+    data: [u8; 65536],
+}
+
+#[derive(UninitProject, DefaultInPlace)]
+#[repr(C)]
+pub struct Table<K, V, const N: usize>
+where
+    K: Copy,
+{
+    pub keys: [K; N],
+    pub values: [V; N],
+    #[placement(default = N)]
+    pub capacity: usize,
+}
+
+impl State {
+    #[must_use]
+    pub fn new_boxed() -> Box<Self> {
+        Box::emplace_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{State, Table};
+
+    use std::cell::Cell;
+    use std::mem::MaybeUninit;
+    use std::panic;
+
+    use placement_new::{overwrite, DefaultInPlace, SlicePlace, UninitProject};
+
+    #[test]
+    fn check_default() {
+        let s = State::new_boxed();
+        assert_eq!(s.config.retries, 0);
+        assert_eq!(s.config.host, "localhost");
+        assert_eq!(s.capacity, 64);
+        assert!(s.names.is_empty());
+        assert!(s.slots.iter().all(Option::is_none));
+        assert!(s.data.iter().all(|&x| x == 0));
+
+        let b: Box<[State]> = Box::emplace_default(2);
+        assert!(b.iter().all(|s| s.capacity == 64));
+    }
+
+    #[test]
+    fn check_generic() {
+        let t: Box<Table<u32, Option<String>, 128>> = placement_new::SinglePlace::emplace_default();
+        assert!(t.keys.iter().all(|&k| k == 0));
+        assert!(t.values.iter().all(Option::is_none));
+        assert_eq!(t.capacity, 128);
+
+        let mut t = MaybeUninit::<Table<u8, u64, 2>>::uninit();
+        let fields = UninitProject::<_>::uninit_project(&mut t);
+        overwrite(&mut fields.keys, [1, 2]);
+        overwrite(&mut fields.values, [3, 4]);
+        overwrite(&mut fields.capacity, 2);
+        let t = unsafe { t.assume_init() };
+        assert_eq!((t.keys, t.values), ([1, 2], [3, 4]));
+    }
+
+    thread_local! {
+        static DROP_COUNT: Cell<usize> = const { Cell::new(0) };
+    }
+
+    struct Counted;
+
+    impl Counted {
+        fn explode() -> Self {
+            panic!("explode")
+        }
+    }
+
+    unsafe impl DefaultInPlace for Counted {
+        fn default_in_place(this: &mut MaybeUninit<Self>) {
+            overwrite(this, Self);
+        }
+    }
+
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            DROP_COUNT.with(|c| c.set(c.get() + 1));
+        }
+    }
+
+    #[derive(UninitProject, DefaultInPlace)]
+    #[repr(C)]
+    struct Partial {
+        a: Counted,
+        b: [Counted; 4],
+        #[placement(default = Counted::explode())]
+        c: Counted,
+    }
+
+    #[test]
+    fn check_unwind() {
+        let ret = panic::catch_unwind(|| {
+            let mut p = MaybeUninit::<Partial>::uninit();
+            Partial::default_in_place(&mut p);
+        });
+        assert!(ret.is_err());
+        assert_eq!(DROP_COUNT.with(Cell::get), 5);
+    }
+}
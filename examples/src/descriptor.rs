@@ -0,0 +1,70 @@
+use std::mem::MaybeUninit;
+
+use placement_new::{memset_zeroed, overwrite, uninit_project, UninitProject};
+
+#[derive(Clone, Copy, UninitProject)]
+#[repr(C)]
+pub struct RxView {
+    pub addr: u64,
+    pub len: u16,
+    pub flags: u16,
+}
+
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct TxView {
+    pub addr: u64,
+    pub cmd: u32,
+}
+
+#[derive(UninitProject)]
+#[repr(C)]
+pub union Desc {
+    pub rx: RxView,
+    pub tx: TxView,
+    pub raw: [u64; 2],
+}
+
+impl Desc {
+    pub fn init_rx(this: &mut MaybeUninit<Self>, addr: u64, len: u16) {
+        memset_zeroed(this);
+        let rx = uninit_project!(uninit_project!(this => union Self => rx));
+        overwrite(&mut rx.addr, addr);
+        overwrite(&mut rx.len, len);
+        overwrite(&mut rx.flags, 1);
+    }
+
+    pub fn init_tx(this: &mut MaybeUninit<Self>, tx: TxView) {
+        overwrite(uninit_project!(this => union Self => tx), tx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Desc, TxView};
+
+    use placement_new::SinglePlace;
+
+    #[test]
+    fn check_union_rx() {
+        let d: Box<Desc> = unsafe { Box::emplace_with(|this| Desc::init_rx(this, 0x1000, 64)) };
+        let rx = unsafe { d.rx };
+        assert_eq!((rx.addr, rx.len, rx.flags), (0x1000, 64, 1));
+        // the padding of `rx` is zeroed by `memset_zeroed`
+        let raw = unsafe { d.raw };
+        let [l0, l1] = 64u16.to_ne_bytes();
+        let [f0, f1] = 1u16.to_ne_bytes();
+        assert_eq!(
+            raw,
+            [0x1000, u64::from_ne_bytes([l0, l1, f0, f1, 0, 0, 0, 0])]
+        );
+    }
+
+    #[test]
+    fn check_union_tx() {
+        let tx = TxView { addr: 8, cmd: 3 };
+        let d: Box<Desc> = unsafe { Box::emplace_with(|this| Desc::init_tx(this, tx)) };
+        let tx = unsafe { d.tx };
+        assert_eq!((tx.addr, tx.cmd), (8, 3));
+    }
+}
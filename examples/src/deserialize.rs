@@ -0,0 +1,231 @@
+use placement_new::{DeserializeInPlace, DeserializeSeqPlace, SinglePlace, UninitProject};
+
+#[derive(UninitProject, DeserializeInPlace)]
+#[repr(C)]
+pub struct Page {
+    id: u32,
+    data: [[u8; 8]; 4],
+}
+
+#[derive(UninitProject, DeserializeInPlace)]
+#[repr(C)]
+pub struct Snapshot {
+    version: u64,
+    name: String,
+    #[placement(default = Vec::new())]
+    tags: Vec<String>,
+    pages: Box<[Page]>,
+}
+
+#[derive(Debug, PartialEq, Eq, UninitProject, DeserializeInPlace)]
+#[repr(C)]
+pub struct Point(i32, i32);
+
+#[derive(Debug, PartialEq, Eq, UninitProject, DeserializeInPlace)]
+#[repr(C)]
+pub struct Tagged<T, const N: usize> {
+    pub tag: u8,
+    pub values: [T; N],
+}
+
+impl Snapshot {
+    /// Loads a snapshot without building it on the stack first.
+    ///
+    /// # Errors
+    /// Returns the error of `deserializer`.
+    pub fn load<'de, D>(deserializer: D) -> Result<Box<Self>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Box::emplace_deserialize(deserializer)
+    }
+}
+
+/// Loads pages directly into a boxed slice.
+///
+/// # Errors
+/// Returns the error of `deserializer`.
+pub fn load_pages<'de, D>(deserializer: D) -> Result<Box<[Page]>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    DeserializeSeqPlace::emplace_deserialize_seq(deserializer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{load_pages, Point, Snapshot, Tagged};
+
+    use std::cell::Cell;
+    use std::mem::MaybeUninit;
+
+    use placement_new::{
+        overwrite, DeserializeInPlace, DeserializeSeqPlace, SinglePlace, UninitProject,
+    };
+    use serde::{Deserialize, Serialize};
+
+    #[test]
+    fn check_struct() {
+        let json = r#"{
+            "version": 3,
+            "name": "snap",
+            "unknown": [1, 2, 3],
+            "pages": [
+                { "id": 1, "data": [[1, 2, 3, 4, 5, 6, 7, 8], [0, 0, 0, 0, 0, 0, 0, 0], [0, 0, 0, 0, 0, 0, 0, 0], [9, 9, 9, 9, 9, 9, 9, 9]] }
+            ]
+        }"#;
+        let mut de = serde_json::Deserializer::from_str(json);
+        let s = Snapshot::load(&mut de).unwrap();
+        assert_eq!(s.version, 3);
+        assert_eq!(s.name, "snap");
+        assert!(s.tags.is_empty());
+        assert_eq!(s.pages.len(), 1);
+        assert_eq!(s.pages[0].id, 1);
+        assert_eq!(s.pages[0].data[0], [1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(s.pages[0].data[3], [9; 8]);
+    }
+
+    #[test]
+    fn check_generic() {
+        let mut de = serde_json::Deserializer::from_str(r#"{ "tag": 1, "values": ["a", "b"] }"#);
+        let t = Box::<Tagged<String, 2>>::emplace_deserialize(&mut de).unwrap();
+        assert_eq!(t.tag, 1);
+        assert_eq!(t.values, ["a", "b"]);
+    }
+
+    #[test]
+    fn check_seq() {
+        let json = r#"[7, "seq", ["a", "b"], []]"#;
+        let mut de = serde_json::Deserializer::from_str(json);
+        let s = Snapshot::load(&mut de).unwrap();
+        assert_eq!(s.version, 7);
+        assert_eq!(s.name, "seq");
+        assert_eq!(s.tags, ["a", "b"]);
+        assert!(s.pages.is_empty());
+
+        let mut de = serde_json::Deserializer::from_str("[1, 2]");
+        let p: Box<Point> = Box::emplace_deserialize(&mut de).unwrap();
+        assert_eq!(*p, Point(1, 2));
+
+        let mut de = serde_json::Deserializer::from_str("[1]");
+        assert!(Box::<Point>::emplace_deserialize(&mut de).is_err());
+    }
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize, UninitProject, DeserializeInPlace)]
+    #[repr(C)]
+    struct Id(u32);
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize, UninitProject, DeserializeInPlace)]
+    #[repr(C)]
+    struct Entry {
+        id: Id,
+        pair: [u8; 2],
+    }
+
+    #[test]
+    fn check_newtype() {
+        let json = serde_json::to_string(&Id(5)).unwrap();
+        assert_eq!(json, "5");
+        assert_eq!(serde_json::from_str::<Id>(&json).unwrap(), Id(5));
+        let mut de = serde_json::Deserializer::from_str(&json);
+        assert_eq!(*Box::<Id>::emplace_deserialize(&mut de).unwrap(), Id(5));
+
+        let entry = Entry {
+            id: Id(7),
+            pair: [1, 2],
+        };
+        let json = serde_json::to_string(&entry).unwrap();
+        let mut de = serde_json::Deserializer::from_str(&json);
+        assert_eq!(*Box::<Entry>::emplace_deserialize(&mut de).unwrap(), entry);
+    }
+
+    #[test]
+    fn check_errors() {
+        let mut de = serde_json::Deserializer::from_str(r#"{ "version": 1, "name": "x" }"#);
+        let err = Snapshot::load(&mut de).err().unwrap();
+        assert!(err.to_string().contains("missing field `pages`"));
+
+        let mut de = serde_json::Deserializer::from_str(r#"{ "version": 1, "version": 2 }"#);
+        let err = Snapshot::load(&mut de).err().unwrap();
+        assert!(err.to_string().contains("duplicate field `version`"));
+
+        let mut de = serde_json::Deserializer::from_str(r#"[{ "id": 1, "data": [] }]"#);
+        let err = load_pages(&mut de).err().unwrap();
+        assert!(err.to_string().contains("an array of length 4"));
+    }
+
+    thread_local! {
+        static DROP_COUNT: Cell<usize> = const { Cell::new(0) };
+    }
+
+    struct Tracked;
+
+    unsafe impl<'de> DeserializeInPlace<'de> for Tracked {
+        fn deserialize_in_place<D>(
+            deserializer: D,
+            this: &mut MaybeUninit<Self>,
+        ) -> Result<(), D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            u32::deserialize(deserializer)?;
+            overwrite(this, Self);
+            Ok(())
+        }
+    }
+
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            DROP_COUNT.with(|c| c.set(c.get() + 1));
+        }
+    }
+
+    #[derive(UninitProject, DeserializeInPlace)]
+    #[repr(C)]
+    struct Pair {
+        a: Tracked,
+        b: Tracked,
+        c: Tracked,
+    }
+
+    #[test]
+    fn check_drop_on_error() {
+        DROP_COUNT.with(|c| c.set(0));
+        let mut de = serde_json::Deserializer::from_str(r#"{ "c": 1, "a": 2, "b": "x" }"#);
+        assert!(Box::<Pair>::emplace_deserialize(&mut de).is_err());
+        assert_eq!(DROP_COUNT.with(Cell::get), 2);
+
+        DROP_COUNT.with(|c| c.set(0));
+        let mut de = serde_json::Deserializer::from_str("[1, 2, 3, -1]");
+        assert!(Vec::<Tracked>::emplace_deserialize_seq(&mut de).is_err());
+        assert_eq!(DROP_COUNT.with(Cell::get), 3);
+
+        DROP_COUNT.with(|c| c.set(0));
+        let mut de = serde_json::Deserializer::from_str("[1, 2]");
+        assert!(Box::<[Tracked; 3]>::emplace_deserialize(&mut de).is_err());
+        assert_eq!(DROP_COUNT.with(Cell::get), 2);
+    }
+
+    #[derive(UninitProject, DeserializeInPlace)]
+    #[repr(C)]
+    struct TrackedPair(Tracked, Tracked);
+
+    #[test]
+    fn check_drop_on_trailing() {
+        // the visitor succeeds, but the deserializer rejects the trailing element
+        DROP_COUNT.with(|c| c.set(0));
+        let mut de = serde_json::Deserializer::from_str("[1, 2, 3, 4]");
+        assert!(Box::<[Tracked; 3]>::emplace_deserialize(&mut de).is_err());
+        assert_eq!(DROP_COUNT.with(Cell::get), 3);
+
+        DROP_COUNT.with(|c| c.set(0));
+        let mut de = serde_json::Deserializer::from_str("[1, 2, 3, 4]");
+        assert!(Box::<Pair>::emplace_deserialize(&mut de).is_err());
+        assert_eq!(DROP_COUNT.with(Cell::get), 3);
+
+        DROP_COUNT.with(|c| c.set(0));
+        let mut de = serde_json::Deserializer::from_str("[1, 2, 3]");
+        assert!(Box::<TrackedPair>::emplace_deserialize(&mut de).is_err());
+        assert_eq!(DROP_COUNT.with(Cell::get), 2);
+    }
+}
@@ -0,0 +1,100 @@
+use std::mem::MaybeUninit;
+
+use placement_new::{SinglePlace, UninitPtr, UninitPtrProject};
+
+#[derive(Clone, Copy, UninitPtrProject)]
+#[repr(C, packed)]
+pub struct Extent {
+    pub start: u64,
+    pub len: u32,
+}
+
+#[derive(UninitPtrProject)]
+#[repr(C, packed)]
+pub struct DiskHeader {
+    pub magic: [u8; 4],
+    pub version: u16,
+    pub block_count: u64,
+    pub extents: [Extent; 4],
+    pub reserved: [u8; 64],
+}
+
+#[derive(UninitPtrProject)]
+#[repr(C, packed)]
+pub struct Tagged<T: Copy> {
+    pub tag: u8,
+    pub value: T,
+}
+
+#[derive(UninitPtrProject)]
+#[repr(C, packed)]
+pub struct Pair<'a, T>(pub u8, pub &'a T)
+where
+    T: Copy;
+
+impl DiskHeader {
+    pub const MAGIC: [u8; 4] = *b"PNFS";
+
+    pub fn init(this: &mut MaybeUninit<Self>, block_count: u64, first: Extent) {
+        let mut fields = UninitPtr::new(this).project();
+        fields.magic.write_unaligned(Self::MAGIC);
+        fields.version.write_unaligned(3);
+        fields.block_count.write_unaligned(block_count);
+        fields.extents.reborrow().write_bytes(0);
+        if let Some(extent) = fields.extents.get(0) {
+            let mut extent = extent.project();
+            extent.start.write_unaligned(first.start);
+            extent.len.write_unaligned(first.len);
+        }
+        fields.reserved.write_bytes(0);
+    }
+
+    #[must_use]
+    pub fn new_boxed(block_count: u64, first: Extent) -> Box<Self> {
+        unsafe { Box::emplace_with(|this| Self::init(this, block_count, first)) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DiskHeader, Extent, Pair, Tagged};
+
+    use std::mem::MaybeUninit;
+
+    use placement_new::UninitPtr;
+
+    #[test]
+    fn check_packed() {
+        let h = DiskHeader::new_boxed(1024, Extent { start: 8, len: 16 });
+        let (magic, version, block_count) = (h.magic, h.version, h.block_count);
+        assert_eq!(magic, DiskHeader::MAGIC);
+        assert_eq!((version, block_count), (3, 1024));
+
+        let extents = h.extents;
+        let (start, len) = (extents[0].start, extents[0].len);
+        assert_eq!((start, len), (8, 16));
+        let (start, len) = (extents[3].start, extents[3].len);
+        assert_eq!((start, len), (0, 0));
+        assert!(h.reserved.iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn check_generic() {
+        let mut t = MaybeUninit::<Tagged<u64>>::uninit();
+        let mut fields = UninitPtr::new(&mut t).project();
+        fields.tag.write_unaligned(1);
+        fields.value.write_unaligned(u64::MAX);
+        let t = unsafe { t.assume_init() };
+        let (tag, value) = (t.tag, t.value);
+        assert_eq!((tag, value), (1, u64::MAX));
+
+        let x = 7_u32;
+        let mut p = MaybeUninit::<Pair<'_, u32>>::uninit();
+        let mut fields = UninitPtr::new(&mut p).project();
+        fields.0.write_unaligned(2);
+        fields.1.write_unaligned(&x);
+        let p = unsafe { p.assume_init() };
+        let (tag, value) = (p.0, p.1);
+        assert_eq!((tag, *value), (2, 7));
+    }
+}
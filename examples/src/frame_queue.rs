@@ -0,0 +1,164 @@
+use std::convert::TryFrom;
+use std::mem::MaybeUninit;
+
+use placement_new::{
+    overwrite, uninit_project, DefaultInPlace, InlineVec, SinglePlace, UninitProject,
+};
+
+pub const FRAME_SIZE: usize = 4096;
+
+#[derive(UninitProject, DefaultInPlace)]
+#[repr(C)]
+pub struct Frame {
+    pub id: u32,
+    pub data: [u8; FRAME_SIZE],
+}
+
+#[derive(UninitProject, DefaultInPlace)]
+#[repr(C)]
+pub struct FrameQueue {
+    pub name: String,
+    pub frames: InlineVec<Frame, 64>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct QueueFull;
+
+impl FrameQueue {
+    #[must_use]
+    pub fn new_boxed() -> Box<Self> {
+        Box::emplace_default()
+    }
+
+    /// Appends a frame filled with `fill`, without building it on the stack.
+    ///
+    /// # Errors
+    /// Returns `QueueFull` if the queue has no room.
+    pub fn push_filled(&mut self, id: u32, fill: u8) -> Result<&mut Frame, QueueFull> {
+        if self.frames.is_full() {
+            return Err(QueueFull);
+        }
+        Ok(unsafe {
+            self.frames
+                .emplace_push_with(|this| init_frame(this, id, fill))
+        })
+    }
+
+    /// Appends `count` zeroed frames with consecutive ids.
+    ///
+    /// # Panics
+    /// Panics if the queue has no room for `count` frames.
+    pub fn push_zeroed(&mut self, first_id: u32, count: usize) -> &mut [Frame] {
+        unsafe {
+            self.frames.extend_with(count, |idx, this| {
+                init_frame(this, first_id + u32::try_from(idx).unwrap(), 0);
+            })
+        }
+    }
+}
+
+fn init_frame(this: &mut MaybeUninit<Frame>, id: u32, fill: u8) {
+    let this = uninit_project!(this);
+    overwrite(&mut this.id, id);
+    for b in uninit_project!(&mut this.data) {
+        overwrite(b, fill);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{init_frame, FrameQueue};
+
+    use std::mem::MaybeUninit;
+    use std::panic;
+    use std::rc::Rc;
+
+    use placement_new::{overwrite, InlineVec};
+
+    #[test]
+    fn check_push() {
+        let mut q = FrameQueue::new_boxed();
+        assert!(q.name.is_empty());
+        assert!(q.frames.is_empty());
+        assert_eq!(q.frames.capacity(), 64);
+
+        q.push_filled(1, 0xab).unwrap();
+        q.push_zeroed(2, 3);
+        assert_eq!(q.frames.len(), 4);
+        let ids: Vec<u32> = q.frames.iter().map(|f| f.id).collect();
+        assert_eq!(ids, [1, 2, 3, 4]);
+        assert!(q.frames[0].data.iter().all(|&b| b == 0xab));
+        assert!(q.frames[3].data.iter().all(|&b| b == 0));
+
+        for id in 5..=64 {
+            q.push_filled(id, 0).unwrap();
+        }
+        assert!(q.frames.is_full());
+        assert!(q.push_filled(65, 0).is_err());
+
+        q.frames.truncate(2);
+        assert_eq!(q.frames.len(), 2);
+        assert_eq!(q.frames.pop().map(|f| f.id), Some(2));
+    }
+
+    #[test]
+    fn check_truncate_drop() {
+        let rc = Rc::new(());
+        let mut v: InlineVec<Rc<()>, 8> = InlineVec::new();
+        for _ in 0..5 {
+            v.push(Rc::clone(&rc));
+        }
+        assert_eq!(Rc::strong_count(&rc), 6);
+        v.truncate(2);
+        assert_eq!(Rc::strong_count(&rc), 3);
+        v.truncate(4);
+        assert_eq!(v.len(), 2);
+        drop(v);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    #[test]
+    fn check_try_push() {
+        let mut v: InlineVec<u32, 4> = InlineVec::new();
+        let r = unsafe {
+            v.try_emplace_push_with(|this| {
+                overwrite(this, 1);
+                Ok::<(), ()>(())
+            })
+        };
+        assert_eq!(r.copied(), Ok(1));
+        let r = unsafe { v.try_emplace_push_with(|_| Err("bad")) };
+        assert_eq!(r, Err("bad"));
+        assert_eq!(&*v, &[1]);
+
+        let mut q = FrameQueue::new_boxed();
+        let frame = unsafe {
+            q.frames.try_emplace_push_with(|this: &mut MaybeUninit<_>| {
+                init_frame(this, 9, 1);
+                Ok::<(), ()>(())
+            })
+        };
+        assert_eq!(frame.map(|f| f.id), Ok(9));
+    }
+
+    #[test]
+    fn check_extend_panic() {
+        let rc = Rc::new(());
+        let mut v: InlineVec<Rc<()>, 8> = InlineVec::new();
+        v.push(Rc::clone(&rc));
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| unsafe {
+            v.extend_with(4, |idx, this| {
+                assert!(idx < 2);
+                overwrite(this, Rc::clone(&rc));
+            });
+        }));
+        assert!(result.is_err());
+        assert_eq!(v.len(), 1);
+        assert_eq!(Rc::strong_count(&rc), 2);
+
+        let slice = unsafe { v.extend_with(3, |_, this| overwrite(this, Rc::clone(&rc))) };
+        assert_eq!(slice.len(), 3);
+        assert_eq!(v.len(), 4);
+        assert_eq!(Rc::strong_count(&rc), 5);
+    }
+}
@@ -0,0 +1,98 @@
+use std::mem::MaybeUninit;
+
+use placement_new::{overwrite, DefaultInPlace, LazyBox, StaticPlace, UninitProject};
+
+pub static SQUARES: StaticPlace<[u64; 1 << 20]> = StaticPlace::new();
+
+/// Returns `n * n` from a table of 8 MiB, which is built in place on first use.
+#[must_use]
+pub fn square(n: usize) -> u64 {
+    let table = unsafe {
+        SQUARES.get_or_emplace_with(|this| {
+            let slots: &mut [MaybeUninit<u64>; 1 << 20] = UninitProject::uninit_project(this);
+            for (slot, n) in slots.iter_mut().zip(0..) {
+                overwrite(slot, n * n);
+            }
+        })
+    };
+    table[n]
+}
+
+#[derive(UninitProject, DefaultInPlace)]
+#[repr(C)]
+pub struct Registry {
+    pub names: Vec<String>,
+    pub slots: [u32; 1 << 16],
+}
+
+pub static REGISTRY: LazyBox<Registry> = LazyBox::new_default();
+
+#[cfg(test)]
+mod tests {
+    use super::{square, REGISTRY, SQUARES};
+
+    use std::panic;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+
+    use placement_new::{LazyBox, StaticPlace};
+
+    #[test]
+    fn check_static() {
+        let handles: Vec<_> = (0..4)
+            .map(|n| thread::spawn(move || square(n * 1000)))
+            .collect();
+        for (n, handle) in handles.into_iter().enumerate() {
+            assert_eq!(handle.join().unwrap(), (n as u64 * 1000).pow(2));
+        }
+        assert_eq!(SQUARES.get().map(|t| t[3]), Some(9));
+    }
+
+    #[test]
+    fn check_once() {
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        static SLOT: StaticPlace<[u8; 64]> = StaticPlace::new();
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                thread::spawn(|| unsafe {
+                    SLOT.get_or_emplace_with(|this| {
+                        CALLS.fetch_add(1, Ordering::Relaxed);
+                        this.write([1; 64]);
+                    })[0]
+                })
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), 1);
+        }
+        assert_eq!(CALLS.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn check_poison() {
+        let slot: StaticPlace<u32> = StaticPlace::new();
+        let ret = panic::catch_unwind(|| unsafe { slot.get_or_emplace_with(|_| panic!("boom")) });
+        assert!(ret.is_err());
+        assert!(slot.is_poisoned());
+        assert!(slot.get().is_none());
+
+        let ret = panic::catch_unwind(|| *slot.get_or_init_zeroed());
+        assert!(ret.is_err());
+    }
+
+    #[test]
+    fn check_lazy() {
+        assert!(REGISTRY.names.is_empty());
+        assert!(REGISTRY.slots.iter().all(|&s| s == 0));
+
+        let lazy = unsafe {
+            LazyBox::new(|this: &mut std::mem::MaybeUninit<Vec<u8>>| {
+                this.write(vec![7; 3]);
+            })
+        };
+        assert!(LazyBox::get(&lazy).is_none());
+        assert_eq!(*lazy, [7, 7, 7]);
+        assert!(LazyBox::get(&lazy).is_some());
+    }
+}
@@ -1,5 +1,4 @@
 use std::mem::MaybeUninit;
-use std::ptr;
 
 use placement_new::{memset_zeroed, overwrite, uninit_project, SinglePlace, UninitProject};
 
@@ -31,10 +30,21 @@ impl State {
     }
 
     pub fn reset_to_a(&mut self) {
-        let this: *mut Self = self;
+        unsafe { placement_new::reinit_with(self, Self::init_a) }
+    }
+
+    pub fn reset_to_c(&mut self, f: impl FnOnce() -> (u32, u32)) {
         unsafe {
-            ptr::drop_in_place(this);
-            Self::init_a(&mut *this.cast())
+            placement_new::reinit_with_or(
+                self,
+                |this| {
+                    let (x, y) = f();
+                    let this = uninit_project!(this => enum Self => C);
+                    overwrite(&mut this.0, x);
+                    overwrite(&mut this.1, y);
+                },
+                || Self::A,
+            );
         }
     }
 
@@ -60,3 +70,27 @@ fn check_boxed() {
 
     drop(s);
 }
+
+#[test]
+fn check_reinit_unwind() {
+    use std::panic;
+
+    let mut s = State::new_boxed_e();
+
+    s.reset_to_c(|| (1, 2));
+    match *s {
+        State::C(x, y) => assert_eq!((x, y), (1, 2)),
+        _ => panic!(),
+    }
+
+    let ret = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        s.reset_to_c(|| panic!());
+    }));
+    assert!(ret.is_err());
+    match *s {
+        State::A => {}
+        _ => panic!(),
+    }
+
+    drop(s);
+}
@@ -0,0 +1,82 @@
+use std::fmt::Write;
+
+use placement_new::{FieldInfo, LayoutInfo};
+
+fn dump_fields(out: &mut String, indent: &str, fields: &[FieldInfo]) {
+    for f in fields {
+        let _ = writeln!(
+            out,
+            "{}{:>4} {:>4} {:>2} {}: {}",
+            indent, f.offset, f.size, f.align, f.name, f.type_name
+        );
+    }
+}
+
+/// Dumps the layout of `T` as a table of `offset size align name: type`.
+#[must_use]
+pub fn dump<T: LayoutInfo>() -> String {
+    let mut out = String::new();
+    dump_fields(&mut out, "", T::LAYOUT);
+    if let Some(tag) = T::TAG {
+        let _ = writeln!(out, "{:>4} {:>4}    <tag>", tag.offset, tag.size);
+    }
+    for v in T::VARIANTS {
+        let _ = writeln!(out, "{}:", v.name);
+        dump_fields(&mut out, "  ", v.fields);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::dump;
+    use crate::wire_record::{Frame, Payload, Record};
+
+    use placement_new::{FieldInfo, LayoutInfo, TagInfo};
+
+    #[test]
+    fn check_struct() {
+        let names: Vec<_> = Record::LAYOUT.iter().map(|f| f.name).collect();
+        assert_eq!(
+            names,
+            ["kind", "urgent", "reserved", "id", "sym", "values", "payload"]
+        );
+        assert_eq!(
+            Record::LAYOUT[5],
+            FieldInfo {
+                name: "values",
+                offset: 16,
+                size: 8,
+                align: 2,
+                type_name: "[u16; 4]",
+            }
+        );
+        assert_eq!(Record::LAYOUT[6].offset, 24);
+        assert!(Record::VARIANTS.is_empty());
+        assert!(Record::TAG.is_none());
+    }
+
+    #[test]
+    fn check_generic() {
+        let items = &Frame::<u64, 3>::LAYOUT[1];
+        assert_eq!((items.name, items.offset, items.size), ("items", 8, 24));
+        assert_eq!(items.type_name, "[T; N]");
+        assert_eq!(Frame::<u8, 3>::LAYOUT[1].offset, 4);
+    }
+
+    #[test]
+    fn check_enum() {
+        assert!(Payload::LAYOUT.is_empty());
+        assert_eq!(Payload::TAG, Some(TagInfo { offset: 0, size: 4 }));
+        let point = &Payload::VARIANTS[1];
+        assert_eq!(point.name, "Point");
+        assert_eq!((point.fields[0].name, point.fields[0].offset), ("x", 4));
+        assert_eq!((point.fields[1].name, point.fields[1].offset), ("y", 8));
+        let flag = &Payload::VARIANTS[2];
+        assert_eq!((flag.fields[0].name, flag.fields[0].offset), ("0", 4));
+        assert_eq!(flag.fields[0].type_name, "bool");
+
+        let text = dump::<Payload>();
+        assert!(text.contains("Point:\n     4    4  4 x: i32\n"));
+    }
+}
@@ -2,6 +2,30 @@
 
 pub mod array_init;
 pub mod boxed_slice;
+pub mod clone_state;
+pub mod default_state;
+pub mod descriptor;
+pub mod deserialize;
+pub mod disk_header;
+pub mod frame_queue;
+pub mod global_table;
 pub mod large_enum;
 pub mod large_struct;
+pub mod layout_dump;
+pub mod map_in_place;
+#[cfg(target_os = "linux")]
+pub mod mmap_state;
+pub mod named_mirror;
+pub mod nested_state;
+pub mod packet;
+pub mod par_init;
+pub mod pool;
+pub mod read_bytes;
+pub mod recycle;
+pub mod report;
+pub mod scratch;
+pub mod shared_place;
 pub mod tagged_data;
+pub mod wire_record;
+pub mod wrapped_state;
+pub mod zeroed;
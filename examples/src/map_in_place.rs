@@ -0,0 +1,102 @@
+use placement_new::MapInPlace;
+
+/// A record in big endian.
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct RawRecord {
+    key: u64,
+    value: u64,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct Parsed {
+    key: u64,
+    value: u64,
+}
+
+impl RawRecord {
+    #[must_use]
+    pub const fn parse(self) -> Parsed {
+        Parsed {
+            key: u64::from_be(self.key),
+            value: u64::from_be(self.value),
+        }
+    }
+}
+
+#[must_use]
+pub fn parse_all(records: Box<[RawRecord]>) -> Box<[Parsed]> {
+    records.map_in_place(|_, record| record.parse())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_all, Parsed, RawRecord};
+
+    use std::panic;
+    use std::rc::Rc;
+
+    use placement_new::MapInPlace;
+
+    #[test]
+    fn check_parse_all() {
+        let records: Box<[RawRecord]> = (0..64_u64)
+            .map(|i| RawRecord {
+                key: i.to_be(),
+                value: (i * 2).to_be(),
+            })
+            .collect();
+        let addr = records.as_ptr().cast::<u8>();
+
+        let parsed = parse_all(records);
+        assert_eq!(parsed.as_ptr().cast::<u8>(), addr);
+        for (i, p) in (0..64_u64).zip(parsed.iter()) {
+            assert_eq!(
+                *p,
+                Parsed {
+                    key: i,
+                    value: i * 2
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn check_vec() {
+        let v: Vec<u32> = (0..100).collect();
+        let cap = v.capacity();
+
+        let v: Vec<f32> = v.map_in_place(|_, x| f32::from_bits(x));
+        assert_eq!(v.capacity(), cap);
+
+        let v: Vec<u64> = v.map_in_place(|idx, x| u64::from(x.to_bits()) + idx as u64);
+        assert_eq!(v.len(), 100);
+        assert!(v.iter().enumerate().all(|(idx, &x)| x == 2 * idx as u64));
+    }
+
+    #[test]
+    fn check_unwind() {
+        let counter = Rc::new(());
+        let v = vec![Rc::clone(&counter); 32];
+
+        let ret = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            v.map_in_place(|idx, x| {
+                assert!(idx < 16);
+                x
+            })
+        }));
+        assert!(ret.is_err());
+        assert_eq!(Rc::strong_count(&counter), 1);
+
+        let b = vec![Rc::clone(&counter); 32].into_boxed_slice();
+        let ret = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            b.map_in_place(|idx, x| {
+                assert!(idx < 16);
+                (x, idx)
+            })
+        }));
+        assert!(ret.is_err());
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+}
@@ -0,0 +1,163 @@
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::path::Path;
+
+use placement_new::{FromBytesInPlace, MmapBox, SlicePlace, UninitProject, Zeroable};
+
+#[derive(Debug, Zeroable, UninitProject, FromBytesInPlace)]
+#[repr(C)]
+pub struct Counters {
+    pub hits: u64,
+    pub misses: u64,
+    pub enabled: bool,
+    pub buckets: [u32; 1024],
+}
+
+fn open_rw(path: &Path) -> io::Result<File> {
+    OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+}
+
+impl Counters {
+    /// Opens the counters persisted at `path`, or creates zeroed ones.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be opened or has a wrong size.
+    pub fn open(path: &Path) -> io::Result<MmapBox<Self>> {
+        let file = open_rw(path)?;
+        // the file is only written by this type
+        unsafe { MmapBox::map_file_zeroed(&file) }
+    }
+
+    /// Opens the counters persisted at `path`, and validates them.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be opened or contains invalid counters.
+    pub fn open_checked(path: &Path) -> io::Result<MmapBox<Self>> {
+        let file = OpenOptions::new().read(true).write(true).open(path)?;
+        unsafe { MmapBox::map_file_from_bytes(&file) }
+    }
+}
+
+/// Creates a huge zeroed table, whose pages are allocated on first write.
+#[must_use]
+pub fn sparse_table(len: usize) -> MmapBox<[u64]> {
+    MmapBox::new_zeroed_slice_safe(len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{open_rw, sparse_table, Counters};
+
+    use std::env;
+    use std::fs;
+    use std::io;
+    use std::mem::MaybeUninit;
+    use std::path::PathBuf;
+    use std::process;
+    use std::rc::Rc;
+
+    use placement_new::{overwrite, MmapBox, SinglePlace, SlicePlace};
+
+    fn temp_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("placement-new-{}-{}", process::id(), name))
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn check_sparse() {
+        let mut t = sparse_table(1 << 28);
+        assert_eq!(t.len(), 1 << 28);
+        t[12345] = 1;
+        t[(1 << 28) - 1] = 2;
+        assert_eq!(t[0], 0);
+        assert_eq!(t[12345], 1);
+        assert_eq!(t[(1 << 28) - 1], 2);
+    }
+
+    #[test]
+    fn check_anonymous() {
+        let rc = Rc::new(());
+        let b: MmapBox<[Rc<()>]> = unsafe {
+            SlicePlace::emplace_with(3, |slice: &mut [MaybeUninit<_>]| {
+                for this in slice {
+                    overwrite(this, Rc::clone(&rc));
+                }
+            })
+        };
+        assert_eq!(Rc::strong_count(&rc), 4);
+        drop(b);
+        assert_eq!(Rc::strong_count(&rc), 1);
+
+        let r = unsafe { MmapBox::<u64>::try_emplace_with(|_: &mut MaybeUninit<u64>| Err("bad")) };
+        assert_eq!(r.err(), Some("bad"));
+
+        let empty: MmapBox<[u64]> = SlicePlace::new_zeroed_slice_safe(0);
+        assert!(empty.is_empty());
+        assert_eq!(format!("{:?}", MmapBox::<u8>::new_zeroed_safe()), "0");
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn check_persist() -> io::Result<()> {
+        let path = temp_path("counters");
+        let _ = fs::remove_file(&path);
+
+        {
+            let mut c = Counters::open(&path)?;
+            assert_eq!(c.hits, 0);
+            assert!(!c.enabled);
+            c.hits = 3;
+            c.enabled = true;
+            c.buckets[7] = 42;
+            c.flush()?;
+        }
+        {
+            let c = Counters::open_checked(&path)?;
+            assert_eq!(c.hits, 3);
+            assert!(c.enabled);
+            assert_eq!(c.buckets[7], 42);
+        }
+
+        let mut bytes = fs::read(&path)?;
+        bytes[16] = 2; // `enabled` is an invalid bool
+        fs::write(&path, &bytes)?;
+        let err = Counters::open_checked(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        fs::write(&path, &bytes[..100])?;
+        let err = Counters::open(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        fs::remove_file(&path)
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn check_persist_slice() -> io::Result<()> {
+        let path = temp_path("slice");
+        let _ = fs::remove_file(&path);
+
+        {
+            let file = open_rw(&path)?;
+            let mut s = unsafe { MmapBox::<[u32]>::map_file_zeroed_slice(&file, 5)? };
+            s.copy_from_slice(&[1, 2, 3, 4, 5]);
+        }
+        assert_eq!(fs::metadata(&path)?.len(), 20);
+        {
+            let file = open_rw(&path)?;
+            let s = unsafe { MmapBox::<[u32]>::map_file_from_bytes_slice(&file)? };
+            assert_eq!(&*s, &[1, 2, 3, 4, 5]);
+            let err = unsafe { MmapBox::<[u64]>::map_file_from_bytes_slice(&file).unwrap_err() };
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+            let err = unsafe { MmapBox::<[u32]>::map_file_zeroed_slice(&file, 4).unwrap_err() };
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        }
+
+        fs::remove_file(&path)
+    }
+}
@@ -0,0 +1,99 @@
+use std::mem::MaybeUninit;
+
+use placement_new::{overwrite, uninit_project, SinglePlace, UninitProject, UninitProjectExt};
+
+#[derive(UninitProject)]
+#[uninit_project(name = UninitBuffers)]
+#[repr(C)]
+pub struct Buffers {
+    pub rx: [u8; 256],
+    pub tx: [u8; 256],
+}
+
+fn init_buffers(this: &mut UninitBuffers) {
+    overwrite(&mut this.rx, [0; 256]);
+    overwrite(&mut this.tx, [0xff; 256]);
+}
+
+#[derive(UninitProject)]
+#[uninit_project(name = UninitConn, vis = pub(crate))]
+#[repr(C)]
+pub struct Conn {
+    pub id: u32,
+    #[uninit_project(nested)]
+    pub buffers: Buffers,
+}
+
+impl Conn {
+    #[must_use]
+    pub fn new_boxed(id: u32) -> Box<Self> {
+        unsafe {
+            Box::emplace_with(|this: &mut MaybeUninit<Self>| {
+                let this: &mut UninitConn = uninit_project!(this);
+                init_buffers(&mut this.buffers);
+                overwrite(&mut this.id, id);
+            })
+        }
+    }
+}
+
+#[derive(UninitProject)]
+#[uninit_project(vis = pub(crate))]
+#[repr(C)]
+pub enum Message {
+    #[uninit_project(name = UninitPing)]
+    Ping { seq: u64 },
+    #[uninit_project(name = UninitData)]
+    Data { len: usize, bytes: [u8; 64] },
+}
+
+impl Message {
+    pub fn init_ping(this: &mut MaybeUninit<Self>, seq: u64) {
+        let this: &mut UninitPing = uninit_project!(this => enum Self => Ping);
+        overwrite(&mut this.seq, seq);
+    }
+}
+
+/// Initializes every `T` whose mirror can be filled by `f`.
+pub fn emplace_via_mirror<T>(f: impl FnOnce(&mut T::Uninit)) -> Box<T>
+where
+    T: UninitProjectExt + UninitProject<T::Uninit>,
+{
+    unsafe { Box::emplace_with(|this| f(T::uninit_project_ext(this))) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{emplace_via_mirror, Buffers, Conn, Message};
+
+    use std::convert::TryFrom;
+
+    use placement_new::{overwrite, SinglePlace};
+
+    #[test]
+    fn check_named_struct() {
+        let c = Conn::new_boxed(3);
+        assert_eq!(c.id, 3);
+        assert!(c.buffers.rx.iter().all(|&b| b == 0));
+        assert!(c.buffers.tx.iter().all(|&b| b == 0xff));
+    }
+
+    #[test]
+    fn check_named_variant() {
+        let m: Box<Message> = unsafe { Box::emplace_with(|this| Message::init_ping(this, 9)) };
+        assert!(matches!(*m, Message::Ping { seq: 9 }));
+    }
+
+    #[test]
+    fn check_associated_mirror() {
+        let b: Box<Buffers> = emplace_via_mirror(super::init_buffers);
+        assert_eq!((b.rx[0], b.tx[255]), (0, 0xff));
+
+        let a = emplace_via_mirror::<[u16; 4]>(|this| {
+            for (i, x) in this.iter_mut().enumerate() {
+                overwrite(x, u16::try_from(i).unwrap());
+            }
+        });
+        assert_eq!(*a, [0, 1, 2, 3]);
+    }
+}
@@ -0,0 +1,79 @@
+use std::mem::MaybeUninit;
+
+use placement_new::{
+    memset_zeroed, overwrite, uninit_project, DefaultInPlace, SinglePlace, UninitProject,
+};
+
+#[derive(UninitProject, DefaultInPlace)]
+#[repr(C)]
+pub struct Buffers {
+    pub rx: [u8; 4096],
+    pub tx: [u8; 4096],
+}
+
+#[derive(UninitProject, DefaultInPlace)]
+#[repr(C)]
+pub struct NetState {
+    #[uninit_project(nested)]
+    pub buffers: Buffers,
+    pub peers: Vec<u32>,
+}
+
+#[derive(UninitProject, DefaultInPlace)]
+#[repr(C)]
+pub struct Stats {
+    pub count: u64,
+    pub total: u64,
+}
+
+#[derive(UninitProject, DefaultInPlace)]
+#[repr(C)]
+pub struct State {
+    pub id: u64,
+    #[uninit_project(nested)]
+    pub net: NetState,
+    pub stats: Stats,
+}
+
+impl State {
+    #[must_use]
+    pub fn new_boxed(id: u64) -> Box<Self> {
+        unsafe {
+            Box::emplace_with(|this: &mut MaybeUninit<Self>| {
+                memset_zeroed(uninit_project!(this => .net.buffers.rx));
+                overwrite(uninit_project!(this => .net.buffers.tx), [0xff; 4096]);
+                overwrite(uninit_project!(this => .net.peers), Vec::new());
+                overwrite(uninit_project!(this => .stats.count), 0);
+                overwrite(uninit_project!(this => .stats.total), 0);
+
+                let this = uninit_project!(this);
+                overwrite(&mut this.id, id);
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::State;
+
+    use placement_new::SinglePlace;
+
+    #[test]
+    fn check_nested() {
+        let s = State::new_boxed(7);
+        assert_eq!(s.id, 7);
+        assert!(s.net.buffers.rx.iter().all(|&b| b == 0));
+        assert!(s.net.buffers.tx.iter().all(|&b| b == 0xff));
+        assert!(s.net.peers.is_empty());
+        assert_eq!((s.stats.count, s.stats.total), (0, 0));
+    }
+
+    #[test]
+    fn check_nested_derive() {
+        let s: Box<State> = Box::emplace_default();
+        assert_eq!(s.id, 0);
+        assert!(s.net.buffers.tx.iter().all(|&b| b == 0));
+        assert!(s.net.peers.is_empty());
+    }
+}
@@ -0,0 +1,87 @@
+use std::mem::MaybeUninit;
+
+use placement_new::{overwrite, DstPlace, UninitProject};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(C)]
+pub struct Header {
+    pub kind: u8,
+    pub seq: u64,
+}
+
+#[derive(UninitProject)]
+#[repr(C)]
+pub struct Packet {
+    pub hdr: Header,
+    pub flags: u8,
+    pub payload: [u8],
+}
+
+#[derive(UninitProject)]
+#[uninit_project(name = UninitSamples)]
+#[repr(C)]
+pub struct Samples(pub u16, pub [u32]);
+
+impl Packet {
+    #[must_use]
+    pub fn new_boxed(hdr: Header, payload: &[u8]) -> Box<Self> {
+        unsafe {
+            Box::<Self>::emplace_dst_with(payload.len(), |fields, tail| {
+                overwrite(fields.hdr, hdr);
+                overwrite(fields.flags, 0);
+                for (dst, &b) in tail.iter_mut().zip(payload) {
+                    overwrite(dst, b);
+                }
+            })
+        }
+    }
+}
+
+fn fill_samples(fields: UninitSamples<'_>, tail: &mut [MaybeUninit<u32>]) {
+    let UninitSamples(count) = fields;
+    overwrite(count, 7);
+    for (dst, n) in tail.iter_mut().zip(0..) {
+        overwrite(dst, n * n);
+    }
+}
+
+impl Samples {
+    #[must_use]
+    pub fn new_boxed(len: usize) -> Box<Self> {
+        unsafe { Box::<Self>::emplace_dst_zeroed_with(len, fill_samples) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Header, Packet, Samples};
+
+    use std::alloc::Layout;
+    use std::mem;
+    use std::rc::Rc;
+
+    use placement_new::PlaceTarget;
+
+    #[test]
+    fn check_dst() {
+        let hdr = Header { kind: 1, seq: 42 };
+        for len in [0, 1, 7, 8, 9, 100] {
+            let payload: Vec<u8> = (0..=u8::MAX).cycle().take(len).collect();
+            let p = Packet::new_boxed(hdr, &payload);
+            assert_eq!(p.hdr, hdr);
+            assert_eq!(p.flags, 0);
+            assert_eq!(&p.payload, &payload[..]);
+            assert_eq!(Layout::for_value(&*p), Packet::layout(len));
+        }
+        assert_eq!(Packet::layout(9).size(), 17 + 9 + 6);
+        assert_eq!(mem::align_of_val(&*Packet::new_boxed(hdr, &[])), 8);
+    }
+
+    #[test]
+    fn check_tuple_dst() {
+        let s: Rc<Samples> = Samples::new_boxed(5).into();
+        assert_eq!(s.0, 7);
+        assert_eq!(&s.1, &[0, 1, 4, 9, 16]);
+        assert_eq!(Layout::for_value(&*s), Samples::layout(5));
+    }
+}
@@ -0,0 +1,74 @@
+use placement_new::{overwrite, SlicePlace};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    key: u64,
+    name: String,
+}
+
+#[must_use]
+pub fn build_table(len: usize, threads: usize) -> Box<[Entry]> {
+    unsafe {
+        Box::par_emplace_with(len, threads, |idx, this| {
+            overwrite(
+                this,
+                Entry {
+                    key: idx as u64,
+                    name: idx.to_string(),
+                },
+            );
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::build_table;
+
+    use std::mem::MaybeUninit;
+    use std::panic;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use placement_new::{overwrite, par_init_slice_with};
+
+    #[test]
+    fn check_build_table() {
+        for &threads in &[0, 1, 3, 8] {
+            let table = build_table(1000, threads);
+            assert_eq!(table.len(), 1000);
+            for (idx, entry) in table.iter().enumerate() {
+                assert_eq!(entry.key, idx as u64);
+                assert_eq!(entry.name, idx.to_string());
+            }
+        }
+    }
+
+    #[test]
+    fn check_unwind() {
+        static CREATE_COUNT: AtomicUsize = AtomicUsize::new(0);
+        static DROP_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+        struct Foo;
+
+        impl Drop for Foo {
+            fn drop(&mut self) {
+                DROP_COUNT.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let mut slice: Vec<MaybeUninit<Foo>> = (0..256).map(|_| MaybeUninit::uninit()).collect();
+
+        let ret = panic::catch_unwind(panic::AssertUnwindSafe(|| unsafe {
+            par_init_slice_with(&mut slice, 4, |idx, this| {
+                assert!(idx != 200);
+                CREATE_COUNT.fetch_add(1, Ordering::Relaxed);
+                overwrite(this, Foo);
+            });
+        }));
+        assert!(ret.is_err());
+
+        let created = CREATE_COUNT.load(Ordering::Relaxed);
+        assert!(created >= 200);
+        assert_eq!(DROP_COUNT.load(Ordering::Relaxed), created);
+    }
+}
@@ -0,0 +1,76 @@
+use std::mem::MaybeUninit;
+
+use placement_new::{memset_zeroed, overwrite, uninit_project, Pool, PoolBox, UninitProject};
+
+#[derive(UninitProject)]
+#[repr(C)]
+pub struct Connection {
+    id: u64,
+    peer: String,
+    buf: [u8; 16384],
+}
+
+impl Connection {
+    pub fn init(this: &mut MaybeUninit<Self>, id: u64, peer: String) {
+        let this = uninit_project!(this);
+        overwrite(&mut this.id, id);
+        overwrite(&mut this.peer, peer);
+        memset_zeroed(&mut this.buf);
+    }
+
+    pub fn open(pool: &Pool<Self>, id: u64, peer: String) -> PoolBox<'_, Self> {
+        unsafe { pool.emplace_with(|this| Self::init(this, id, peer)) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Connection;
+
+    use std::{panic, ptr};
+
+    use placement_new::{overwrite, Pool};
+
+    #[test]
+    fn check_reuse() {
+        let pool = Pool::new();
+        pool.reserve(2);
+        assert_eq!(pool.free_slots(), 2);
+
+        let a = Connection::open(&pool, 1, String::from("a"));
+        let addr = ptr::addr_of!(*a);
+        assert_eq!(pool.free_slots(), 1);
+        assert_eq!(a.id, 1);
+        assert_eq!(a.peer, "a");
+        drop(a);
+        assert_eq!(pool.free_slots(), 2);
+
+        let b = Connection::open(&pool, 2, String::from("b"));
+        assert_eq!(ptr::addr_of!(*b), addr);
+        assert_eq!(b.id, 2);
+        assert!(b.buf.iter().all(|&x| x == 0));
+    }
+
+    #[test]
+    fn check_zero_on_return() {
+        let pool: Pool<[u8; 4096]> = Pool::new_zeroed();
+
+        let mut a = unsafe { pool.emplace_with(|this| overwrite(this, [0xff; 4096])) };
+        a[0] = 1;
+        drop(a);
+
+        let b = unsafe { pool.emplace_zeroed_with(|_| {}) };
+        assert!(b.iter().all(|&x| x == 0));
+    }
+
+    #[test]
+    fn check_unwind() {
+        let pool: Pool<Connection> = Pool::new();
+
+        let ret = panic::catch_unwind(panic::AssertUnwindSafe(|| unsafe {
+            pool.emplace_with(|_| panic!())
+        }));
+        assert!(ret.is_err());
+        assert_eq!(pool.free_slots(), 1);
+    }
+}
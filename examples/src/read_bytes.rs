@@ -0,0 +1,121 @@
+use std::io::{self, Read};
+
+use placement_new::{HeaderSlice, ReadPlace};
+
+#[derive(Debug)]
+pub struct Header {
+    pub version: u16,
+    pub name: String,
+}
+
+pub const MAX_BODY_LEN: usize = 1 << 20;
+
+/// Reads a record: `version: u16`, `name_len: u8`, `name`, `body_len: u32`, `body`.
+///
+/// # Errors
+/// Returns an error if the record is truncated or invalid.
+pub fn read_record<R: Read>(reader: &mut R) -> io::Result<Box<HeaderSlice<Header>>> {
+    HeaderSlice::emplace_from_reader(reader, |reader| {
+        let mut buf = [0; 2];
+        reader.read_exact(&mut buf)?;
+        let version = u16::from_le_bytes(buf);
+
+        let mut name_len = [0; 1];
+        reader.read_exact(&mut name_len)?;
+        let name = Box::<[u8]>::emplace_from_reader(reader, name_len[0].into())?;
+        let name = String::from_utf8(name.into())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let mut buf = [0; 4];
+        reader.read_exact(&mut buf)?;
+        let len = u32::from_le_bytes(buf) as usize;
+        if len > MAX_BODY_LEN {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "body too long"));
+        }
+
+        Ok((Header { version, name }, len))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::read_record;
+
+    use std::convert::TryFrom;
+    use std::io::{self, Read};
+
+    use placement_new::{read_exact_uninit, ReadPlace};
+
+    fn record(body: &[u8]) -> Vec<u8> {
+        let mut buf = vec![3, 0, 4];
+        buf.extend_from_slice(b"test");
+        buf.extend_from_slice(&u32::try_from(body.len()).unwrap().to_le_bytes());
+        buf.extend_from_slice(body);
+        buf
+    }
+
+    /// Returns at most 3 bytes per read and is interrupted every other call.
+    struct Trickle<'a> {
+        data: &'a [u8],
+        interrupt: bool,
+    }
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.interrupt = !self.interrupt;
+            if self.interrupt {
+                return Err(io::ErrorKind::Interrupted.into());
+            }
+            let n = buf.len().min(self.data.len()).min(3);
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn check_record() {
+        let body: Vec<u8> = (0..100).collect();
+        let data = record(&body);
+        let r = read_record(&mut data.as_slice()).unwrap();
+        assert_eq!(r.header.version, 3);
+        assert_eq!(r.header.name, "test");
+        assert_eq!(&r.body, body.as_slice());
+
+        let mut reader = Trickle {
+            data: &data,
+            interrupt: false,
+        };
+        let r = read_record(&mut reader).unwrap();
+        assert_eq!(&r.body, body.as_slice());
+
+        let data = record(&[]);
+        let r = read_record(&mut data.as_slice()).unwrap();
+        assert!(r.body.is_empty());
+    }
+
+    #[test]
+    fn check_truncated() {
+        let data = record(&[1; 16]);
+        for len in 0..data.len() {
+            let err = read_record(&mut &data[..len]).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+        }
+
+        let mut data = record(&[]);
+        data[7..11].copy_from_slice(&u32::MAX.to_le_bytes());
+        let err = read_record(&mut data.as_slice()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn check_read_exact_uninit() {
+        let data: Vec<u8> = (0..=255).cycle().take(20000).collect();
+        let b = Box::<[u8]>::emplace_from_reader(&mut data.as_slice(), data.len()).unwrap();
+        assert_eq!(&*b, data.as_slice());
+
+        let mut buf = [std::mem::MaybeUninit::uninit(); 10];
+        let err = read_exact_uninit(&mut &data[..5], &mut buf).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
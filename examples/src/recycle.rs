@@ -0,0 +1,106 @@
+use std::mem::MaybeUninit;
+
+use placement_new::{overwrite, uninit_project, RecycleBox, SinglePlace, UninitProject};
+
+#[derive(UninitProject)]
+#[repr(C)]
+pub struct Request {
+    id: u64,
+    path: String,
+    body: [u8; 1024],
+}
+
+#[derive(UninitProject)]
+#[repr(C)]
+pub struct Response {
+    id: u64,
+    status: String,
+    body: [u8; 1024],
+}
+
+impl Request {
+    #[must_use]
+    pub fn new_boxed(id: u64, path: String) -> Box<Self> {
+        unsafe {
+            Box::emplace_with(|this: &mut MaybeUninit<Self>| {
+                let this = uninit_project!(this);
+                overwrite(&mut this.id, id);
+                overwrite(&mut this.path, path);
+                overwrite(&mut this.body, [0; 1024]);
+            })
+        }
+    }
+
+    #[must_use]
+    pub fn respond(self: Box<Self>, status: String) -> Box<Response> {
+        let id = self.id;
+        unsafe { self.recycle_into(|this| Response::init(this, id, status)) }
+    }
+}
+
+impl Response {
+    pub fn init(this: &mut MaybeUninit<Self>, id: u64, status: String) {
+        let this = uninit_project!(this);
+        overwrite(&mut this.id, id);
+        overwrite(&mut this.status, status);
+        overwrite(&mut this.body, [0xff; 1024]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Request;
+
+    use std::panic;
+    use std::ptr;
+    use std::rc::Rc;
+
+    use placement_new::{init_slice_with, overwrite, RecycleBox, RecycleBoxedSlice};
+
+    #[test]
+    fn check_recycle() {
+        let req = Request::new_boxed(42, String::from("/index.html"));
+        assert_eq!(req.path, "/index.html");
+        let addr = ptr::addr_of!(*req).cast::<u8>();
+
+        let resp = req.respond(String::from("200 OK"));
+        assert_eq!(ptr::addr_of!(*resp).cast::<u8>(), addr);
+        assert_eq!(resp.id, 42);
+        assert_eq!(resp.status, "200 OK");
+        assert!(resp.body.iter().all(|&x| x == 0xff));
+    }
+
+    #[test]
+    fn check_recycle_slice() {
+        let counter = Rc::new(());
+        let b: Box<[Rc<()>]> = vec![Rc::clone(&counter); 16].into_boxed_slice();
+        assert_eq!(Rc::strong_count(&counter), 17);
+
+        let b: Box<[usize]> = unsafe {
+            b.recycle_into(|slice| {
+                init_slice_with(slice, |idx, this| overwrite(this, idx));
+            })
+        };
+        assert_eq!(Rc::strong_count(&counter), 1);
+        assert!(b.iter().enumerate().all(|(idx, &x)| idx == x));
+    }
+
+    #[test]
+    fn check_recycle_unwind() {
+        let counter = Rc::new(());
+        let b = Box::new(Rc::clone(&counter));
+
+        let ret = panic::catch_unwind(panic::AssertUnwindSafe(|| unsafe {
+            b.recycle_into::<usize>(|_| panic!())
+        }));
+        assert!(ret.is_err());
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "layouts")]
+    fn check_recycle_layout_mismatch() {
+        let b = Box::new(0_u32);
+        let _ = unsafe { b.recycle_into::<u64>(|this| overwrite(this, 0)) };
+    }
+}
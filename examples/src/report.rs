@@ -0,0 +1,86 @@
+use std::ffi::CStr;
+use std::sync::Arc;
+
+use placement_new::{CStrPlace, InteriorNulError, StrPlace};
+
+/// Writes `n` rows into the bytes, each row is `row 0000\n`.
+fn render_rows(buf: &mut [u8], n: usize) {
+    let rows = buf.chunks_exact_mut(ROW_LEN).take(n);
+    for (idx, row) in rows.enumerate() {
+        row.copy_from_slice(format!("row {idx:04}\n").as_bytes());
+    }
+}
+
+const ROW_LEN: usize = 9;
+
+/// Renders a report directly into an `Arc<str>`.
+///
+/// # Panics
+/// Panics if `n` is too large.
+#[must_use]
+pub fn render_report(n: usize) -> Arc<str> {
+    Arc::emplace_str_with(n * ROW_LEN, |buf| render_rows(buf, n)).unwrap()
+}
+
+/// Renders a report directly into a `Box<CStr>`.
+///
+/// # Errors
+/// Returns an error if `name` contains a nul byte.
+pub fn render_name(name: &[u8]) -> Result<Box<CStr>, InteriorNulError> {
+    Box::emplace_cstr_with(name.len(), |buf| buf.copy_from_slice(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render_name, render_report};
+
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    use placement_new::{overwrite, CStrPlace, StrPlace};
+
+    #[test]
+    fn check_str() {
+        let report = render_report(3);
+        assert_eq!(&*report, "row 0000\nrow 0001\nrow 0002\n");
+
+        let boxed: Box<str> = unsafe {
+            Box::emplace_str_unchecked_with(3, |buf| {
+                for (dst, &b) in buf.iter_mut().zip(b"abc") {
+                    overwrite(dst, b);
+                }
+            })
+        };
+        assert_eq!(&*boxed, "abc");
+    }
+
+    #[test]
+    fn check_invalid_utf8() {
+        let err =
+            Rc::<str>::emplace_str_with(4, |buf| buf[..2].copy_from_slice(b"a\xff")).unwrap_err();
+        assert_eq!(err.valid_up_to(), 1);
+
+        let err = Arc::<str>::emplace_str_with(2, |buf| buf[0] = 0xc3).unwrap_err();
+        assert_eq!(err.valid_up_to(), 0);
+    }
+
+    #[test]
+    fn check_cstr() {
+        let name = render_name(b"placement").unwrap();
+        assert_eq!(name.to_bytes_with_nul(), b"placement\0");
+
+        let err = render_name(b"place\0ment").unwrap_err();
+        assert_eq!(err.position(), 5);
+
+        let empty = render_name(b"").unwrap();
+        assert_eq!(empty.to_bytes(), b"");
+
+        let unchecked: Box<std::ffi::CStr> = unsafe {
+            Box::emplace_cstr_unchecked_with(2, |buf| {
+                overwrite(&mut buf[0], b'o');
+                overwrite(&mut buf[1], b'k');
+            })
+        };
+        assert_eq!(unchecked.to_bytes(), b"ok");
+    }
+}
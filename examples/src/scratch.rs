@@ -0,0 +1,149 @@
+use std::fmt::Display;
+
+use placement_new::{
+    inline_box, memset_zeroed, overwrite, uninit_project, with_uninit, InlineBox, UninitProject,
+};
+
+#[derive(UninitProject)]
+#[repr(C)]
+pub struct Scratch {
+    pub buf: [u8; 65536],
+    pub lines: Vec<usize>,
+}
+
+/// Splits `text` into lines, using a scratch buffer of 64 KiB on the stack.
+#[must_use]
+pub fn line_starts(text: &str) -> Vec<usize> {
+    with_uninit::<Scratch, _>(|slot| {
+        let scratch = unsafe {
+            slot.init_with(|this| {
+                let this = uninit_project!(this);
+                memset_zeroed(&mut this.buf);
+                overwrite(&mut this.lines, vec![0]);
+            })
+        };
+        let len = text.len().min(scratch.buf.len());
+        scratch.buf[..len].copy_from_slice(&text.as_bytes()[..len]);
+        for (idx, &b) in scratch.buf[..len].iter().enumerate() {
+            if b == b'\n' {
+                scratch.lines.push(idx + 1);
+            }
+        }
+        scratch.lines.clone()
+    })
+}
+
+pub trait Shape {
+    fn area(&self) -> f64;
+    fn scale(&mut self, k: f64);
+}
+
+pub struct Square(pub f64);
+
+pub struct Rect {
+    pub w: f64,
+    pub h: f64,
+    pub label: String,
+}
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        self.0 * self.0
+    }
+
+    fn scale(&mut self, k: f64) {
+        self.0 *= k;
+    }
+}
+
+impl Shape for Rect {
+    fn area(&self) -> f64 {
+        self.w * self.h
+    }
+
+    fn scale(&mut self, k: f64) {
+        self.w *= k;
+        self.h *= k;
+    }
+}
+
+/// Shapes which are stored inline, without heap allocation for each shape.
+#[must_use]
+pub fn shapes() -> Vec<InlineBox<dyn Shape, 48>> {
+    vec![
+        inline_box!(dyn Shape, Square(2.0)),
+        inline_box!(
+            dyn Shape,
+            Rect {
+                w: 2.0,
+                h: 3.0,
+                label: "rect".into()
+            }
+        ),
+    ]
+}
+
+#[must_use]
+pub fn displayed(value: impl Display + 'static) -> InlineBox<dyn Display, 32> {
+    inline_box!(dyn Display, value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{displayed, line_starts, shapes, Scratch};
+
+    use std::panic;
+    use std::rc::Rc;
+
+    use placement_new::{
+        memset_zeroed, overwrite, uninit_project, with_default, with_uninit, StackSlot,
+    };
+
+    #[test]
+    fn check_with_uninit() {
+        assert_eq!(line_starts("a\nbc\n\nd"), [0, 2, 5, 6]);
+        assert_eq!(with_default(|x: &mut [u64; 4]| x.iter().sum::<u64>()), 0);
+
+        let rc = Rc::new(());
+        let len = with_uninit(|slot| slot.init(vec![Rc::clone(&rc); 3]).len());
+        assert_eq!(len, 3);
+        assert_eq!(Rc::strong_count(&rc), 1);
+        assert_eq!(with_uninit(|_: StackSlot<'_, Rc<()>>| 7), 7);
+    }
+
+    #[test]
+    fn check_drop_on_unwind() {
+        let rc = Rc::new(());
+        let ret = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            with_uninit::<Scratch, ()>(|slot| {
+                unsafe {
+                    slot.init_with(|this| {
+                        let this = uninit_project!(this);
+                        memset_zeroed(&mut this.buf);
+                        overwrite(&mut this.lines, vec![Rc::strong_count(&rc)]);
+                    });
+                }
+                panic!("boom")
+            });
+        }));
+        assert!(ret.is_err());
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    #[test]
+    fn check_inline_box() {
+        let mut shapes = shapes();
+        for shape in &mut shapes {
+            shape.scale(2.0);
+        }
+        let areas: Vec<f64> = shapes.iter().map(|s| s.area()).collect();
+        assert!((areas[0] - 16.0).abs() < 1e-9);
+        assert!((areas[1] - 24.0).abs() < 1e-9);
+
+        let moved = shapes.pop().unwrap();
+        assert!((moved.area() - 24.0).abs() < 1e-9);
+
+        assert_eq!(displayed(42).to_string(), "42");
+        assert_eq!(displayed(String::from("hi")).to_string(), "hi");
+    }
+}
@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use placement_new::{overwrite, Place, PlaceTarget};
+
+/// Fills a slice in any place, which is written once for `Box`, `Rc` and `Arc`.
+#[must_use]
+pub fn iota<P: Place<[u32]>>(len: usize) -> P {
+    unsafe {
+        P::emplace_raw_with(len, |ptr| {
+            for (idx, n) in (0..len).zip(0..) {
+                ptr.cast::<u32>().add(idx).write(n);
+            }
+        })
+    }
+}
+
+/// Copies a string into any place.
+#[must_use]
+pub fn copy_str<P: Place<str>>(s: &str) -> P {
+    unsafe {
+        P::emplace_raw_with(s.len(), |ptr| {
+            ptr.cast::<u8>()
+                .copy_from_nonoverlapping(s.as_ptr(), s.len());
+        })
+    }
+}
+
+/// Allocates any target in any place, zeroed.
+///
+/// # Safety
+/// Zero must be a valid value of `T`.
+pub unsafe fn zeroed<T, P>(meta: T::Metadata) -> P
+where
+    T: ?Sized + PlaceTarget,
+    P: Place<T>,
+{
+    P::emplace_zeroed_raw_with(meta, |_| {})
+}
+
+#[must_use]
+pub fn shared_table() -> Arc<[u64; 8192]> {
+    unsafe {
+        Arc::emplace_raw_with((), |ptr: *mut [u64; 8192]| {
+            for (idx, x) in (*ptr.cast::<[std::mem::MaybeUninit<u64>; 8192]>())
+                .iter_mut()
+                .enumerate()
+            {
+                overwrite(x, idx as u64);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{copy_str, iota, shared_table, zeroed};
+
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    use placement_new::{SinglePlace, SlicePlace};
+
+    #[test]
+    fn check_any_place() {
+        let boxed: Box<[u32]> = iota(5);
+        let rc: Rc<[u32]> = iota(5);
+        let arc: Arc<[u32]> = iota(5);
+        assert_eq!(&*boxed, [0, 1, 2, 3, 4]);
+        assert_eq!(*boxed, *rc);
+        assert_eq!(*rc, *arc);
+
+        let hello: Rc<str> = copy_str("hello");
+        let world: Box<str> = copy_str("world");
+        assert_eq!((&*hello, &*world), ("hello", "world"));
+
+        let zeros: Arc<[u16]> = unsafe { zeroed(3) };
+        assert_eq!(*zeros, [0; 3]);
+        let zero: Box<u64> = unsafe { zeroed(()) };
+        assert_eq!(*zero, 0);
+    }
+
+    #[test]
+    fn check_adapters() {
+        let r: Rc<[u8; 16]> = Rc::new_zeroed_safe();
+        assert_eq!(*r, [0; 16]);
+        let a: Arc<[u8]> = Arc::new_zeroed_slice_safe(4);
+        assert_eq!(*a, [0; 4]);
+
+        let t = shared_table();
+        assert_eq!((t[0], t[8191]), (0, 8191));
+    }
+}
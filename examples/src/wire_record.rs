@@ -0,0 +1,149 @@
+use std::num::NonZeroU32;
+
+use placement_new::{FromBytesError, FromBytesInPlace, LayoutInfo, SinglePlace, UninitProject};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UninitProject, FromBytesInPlace)]
+#[repr(C)]
+pub enum Kind {
+    Request = 1,
+    Response = 2,
+}
+
+#[derive(Debug, PartialEq, Eq, UninitProject, FromBytesInPlace, LayoutInfo)]
+#[repr(C)]
+pub enum Payload {
+    Empty,
+    Point { x: i32, y: i32 },
+    Flag(bool),
+}
+
+#[derive(Debug, UninitProject, FromBytesInPlace, LayoutInfo)]
+#[repr(C)]
+pub struct Record {
+    pub kind: Kind,
+    pub urgent: bool,
+    pub reserved: [u8; 3],
+    pub id: NonZeroU32,
+    pub sym: char,
+    pub values: [u16; 4],
+    pub payload: Payload,
+}
+
+#[derive(Debug, UninitProject, FromBytesInPlace, LayoutInfo)]
+#[repr(C)]
+pub struct Frame<T, const N: usize> {
+    pub len: u32,
+    pub items: [T; N],
+}
+
+impl Record {
+    /// Decodes a record received from the network.
+    ///
+    /// # Errors
+    /// Returns an error if the bytes are not a valid record.
+    pub fn decode(bytes: &[u8]) -> Result<Box<Self>, FromBytesError> {
+        Box::emplace_from_bytes(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Frame, Kind, Payload, Record};
+
+    use std::mem;
+
+    use placement_new::{FromBytesErrorKind, SinglePlace};
+
+    fn encode(kind: u32, urgent: u8, id: u32, sym: u32, tag: u32, payload: [u32; 2]) -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&kind.to_ne_bytes());
+        buf.extend_from_slice(&[urgent, 0, 0, 0]);
+        buf.extend_from_slice(&id.to_ne_bytes());
+        buf.extend_from_slice(&sym.to_ne_bytes());
+        for v in 1..=4_u16 {
+            buf.extend_from_slice(&v.to_ne_bytes());
+        }
+        buf.extend_from_slice(&tag.to_ne_bytes());
+        buf.extend_from_slice(&payload[0].to_ne_bytes());
+        buf.extend_from_slice(&payload[1].to_ne_bytes());
+        buf
+    }
+
+    #[test]
+    fn check_decode() {
+        assert_eq!(mem::size_of::<Record>(), 36);
+
+        let bytes = encode(2, 1, 42, 'x'.into(), 1, [3, 0xffff_fffc]);
+        let r = Record::decode(&bytes).unwrap();
+        assert_eq!(r.kind, Kind::Response);
+        assert!(r.urgent);
+        assert_eq!(r.id.get(), 42);
+        assert_eq!(r.sym, 'x');
+        assert_eq!(r.values, [1, 2, 3, 4]);
+        assert_eq!(r.payload, Payload::Point { x: 3, y: -4 });
+
+        let bytes = encode(1, 0, 1, 'y'.into(), 2, [1, 0]);
+        let r = Record::decode(&bytes).unwrap();
+        assert_eq!(r.kind, Kind::Request);
+        assert_eq!(r.payload, Payload::Flag(true));
+    }
+
+    #[test]
+    fn check_generic() {
+        let mut bytes = 2_u32.to_ne_bytes().to_vec();
+        bytes.extend_from_slice(&[1, 0, 1]);
+        let f = Box::<Frame<bool, 3>>::emplace_from_bytes(&bytes).unwrap();
+        assert_eq!((f.len, f.items), (2, [true, false, true]));
+
+        bytes[5] = 2;
+        let err = Box::<Frame<bool, 3>>::emplace_from_bytes(&bytes).unwrap_err();
+        assert_eq!(
+            (err.offset(), err.kind()),
+            (5, FromBytesErrorKind::InvalidBool)
+        );
+    }
+
+    #[test]
+    fn check_invalid() {
+        let check = |bytes: &[u8], offset: usize, kind: FromBytesErrorKind| {
+            let err = Record::decode(bytes).unwrap_err();
+            assert_eq!((err.offset(), err.kind()), (offset, kind));
+        };
+
+        let c = 'c'.into();
+        check(
+            &encode(3, 0, 1, c, 0, [0; 2]),
+            0,
+            FromBytesErrorKind::InvalidDiscriminant,
+        );
+        check(
+            &encode(1, 2, 1, c, 0, [0; 2]),
+            4,
+            FromBytesErrorKind::InvalidBool,
+        );
+        check(&encode(1, 0, 0, c, 0, [0; 2]), 8, FromBytesErrorKind::Zero);
+        check(
+            &encode(1, 0, 1, 0xd800, 0, [0; 2]),
+            12,
+            FromBytesErrorKind::InvalidChar,
+        );
+        check(
+            &encode(1, 0, 1, c, 3, [0; 2]),
+            24,
+            FromBytesErrorKind::InvalidDiscriminant,
+        );
+        check(
+            &encode(1, 0, 1, c, 2, [5, 0]),
+            28,
+            FromBytesErrorKind::InvalidBool,
+        );
+
+        let bytes = encode(1, 0, 1, c, 1, [0; 2]);
+        check(&bytes[..18], 18, FromBytesErrorKind::UnexpectedEnd);
+        check(&bytes[..30], 28, FromBytesErrorKind::UnexpectedEnd);
+        assert_eq!(
+            Record::decode(&[]).unwrap_err().to_string(),
+            "unexpected end of bytes at offset 0"
+        );
+    }
+}
@@ -0,0 +1,72 @@
+use std::cell::{Cell, UnsafeCell};
+use std::mem::{ManuallyDrop, MaybeUninit};
+use std::num::Wrapping;
+
+use placement_new::{
+    memset_zeroed, overwrite, uninit_project, SinglePlace, UninitProject, UninitProjectTuple,
+};
+
+#[derive(Clone, Copy, UninitProject)]
+#[repr(C)]
+pub struct Counters {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+#[derive(UninitProject)]
+#[repr(C)]
+pub struct Cache {
+    pub slots: ManuallyDrop<[u32; 4]>,
+    #[uninit_project(nested)]
+    pub counters: Cell<Counters>,
+    pub buf: UnsafeCell<[u8; 1024]>,
+    pub epoch: Wrapping<u64>,
+    pub range: (u32, u64),
+    #[uninit_project(nested)]
+    pub limits: (Counters, bool),
+}
+
+impl Cache {
+    #[must_use]
+    pub fn new_boxed(epoch: u64) -> Box<Self> {
+        unsafe {
+            Box::emplace_with(|this: &mut MaybeUninit<Self>| {
+                overwrite(uninit_project!(this => .counters.hits), 0);
+                overwrite(uninit_project!(this => .counters.misses), 0);
+                overwrite(uninit_project!(this => .limits.0.hits), 100);
+                overwrite(uninit_project!(this => .limits.0.misses), 0);
+
+                let this = uninit_project!(this);
+                for slot in uninit_project!(uninit_project!(&mut this.slots)) {
+                    overwrite(slot, u32::MAX);
+                }
+                memset_zeroed(uninit_project!(&mut this.buf));
+                overwrite(uninit_project!(&mut this.epoch), epoch);
+
+                let (start, end) = UninitProjectTuple::uninit_project_tuple(&mut this.range);
+                overwrite(start, 0);
+                overwrite(end, 64);
+
+                overwrite(&mut this.limits.1, true);
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Cache;
+
+    #[test]
+    fn check_wrappers() {
+        let c = Cache::new_boxed(7);
+        assert_eq!(*c.slots, [u32::MAX; 4]);
+        let counters = c.counters.get();
+        assert_eq!((counters.hits, counters.misses), (0, 0));
+        assert!(unsafe { &*c.buf.get() }.iter().all(|&b| b == 0));
+        assert_eq!(c.epoch.0, 7);
+        assert_eq!(c.range, (0, 64));
+        assert_eq!((c.limits.0.hits, c.limits.0.misses), (100, 0));
+        assert!(c.limits.1);
+    }
+}
@@ -0,0 +1,45 @@
+use placement_new::{SinglePlace, SlicePlace, Zeroable};
+
+#[derive(Zeroable)]
+#[repr(C)]
+pub struct Histogram {
+    total: u64,
+    buckets: [u32; 65536],
+    scale: f64,
+    next: Option<Box<Self>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Zeroable)]
+pub struct Point(f32, f32);
+
+impl Histogram {
+    #[must_use]
+    pub fn new_boxed() -> Box<Self> {
+        Box::new_zeroed_safe()
+    }
+
+    pub fn record(&mut self, value: u16) {
+        self.total += 1;
+        self.buckets[usize::from(value)] += 1;
+    }
+}
+
+#[must_use]
+pub fn origins(len: usize) -> Box<[Point]> {
+    Box::new_zeroed_slice_safe(len)
+}
+
+#[test]
+fn check_zeroed() {
+    let mut h = Histogram::new_boxed();
+    assert_eq!(h.total, 0);
+    assert!(h.buckets.iter().all(|&x| x == 0));
+    assert!(h.scale == 0.0);
+    assert!(h.next.is_none());
+
+    h.record(42);
+    assert_eq!((h.total, h.buckets[42]), (1, 1));
+
+    let points = origins(16);
+    assert!(points.iter().all(|&p| p == Point(0.0, 0.0)));
+}
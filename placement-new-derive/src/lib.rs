@@ -13,10 +13,15 @@
 extern crate proc_macro;
 
 use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
 use quote::{format_ident, quote};
 use syn::parse::Parse;
+use syn::spanned::Spanned;
 use syn::token::{Semi, Struct};
-use syn::{Data, DataStruct, DeriveInput, Field, Fields, Ident, ItemStruct, Path, Token, Type};
+use syn::{
+    Attribute, Data, DataStruct, DeriveInput, Expr, Field, Fields, Generics, Ident, Index,
+    ItemStruct, Member, Path, Token, Type, Variant, Visibility,
+};
 
 macro_rules! emit_error {
     ($token:expr, $msg: expr) => {{
@@ -25,14 +30,22 @@ macro_rules! emit_error {
     (@repr_error $token:expr) => {{
         emit_error!(
             $token,
-            "Only repr(C) structs and enums can derive UninitProject"
+            "Only repr(C) structs, enums and unions can derive UninitProject"
         )
     }};
 }
 
 /// Implements `UninitProject` for a type and generates corresponding types.
+///
+/// `#[uninit_project(name = Ident, vis = Visibility)]` names the mirror of a struct,
+/// or of an enum variant, and sets its visibility.
+/// `UninitProjectExt` is implemented for a struct unless its mirror is less visible than itself,
+/// and `#[uninit_project(nested)]` fields require it.
+///
+/// A struct whose last field is a slice implements `PlaceTarget` and `UninitProjectDst` instead,
+/// and the named type holds the references to the other fields.
 #[allow(clippy::missing_panics_doc)]
-#[proc_macro_derive(UninitProject)]
+#[proc_macro_derive(UninitProject, attributes(uninit_project))]
 pub fn uninit_project(input: TokenStream) -> TokenStream {
     impl_UninitProject(&syn::parse(input).unwrap())
 }
@@ -44,6 +57,18 @@ fn impl_UninitProject(ast: &DeriveInput) -> TokenStream {
         None => false,
     });
 
+    let is_packed = ast
+        .attrs
+        .iter()
+        .any(|attr| attr.path.is_ident("repr") && attr.tokens.to_string().contains("packed"));
+
+    if is_packed {
+        emit_error!(
+            ast,
+            "repr(packed) types cannot derive UninitProject, derive UninitPtrProject instead"
+        )
+    }
+
     if !has_repr_c {
         emit_error!(@repr_error ast)
     }
@@ -51,34 +76,369 @@ fn impl_UninitProject(ast: &DeriveInput) -> TokenStream {
     match ast.data {
         Data::Struct(_) => impl_UninitProject_for_struct(ast),
         Data::Enum(_) => impl_UninitProject_for_enum(ast),
-        Data::Union(_) => emit_error!(@repr_error ast),
+        Data::Union(_) => impl_UninitProject_for_union(ast),
     }
 }
 
+#[allow(non_snake_case)]
+fn impl_UninitProject_for_union(ast: &DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+    let Data::Union(ref data) = ast.data else {
+        unreachable!()
+    };
+
+    if !ast.generics.params.is_empty() {
+        emit_error!(&ast.generics, "generic unions cannot derive UninitProject")
+    }
+
+    let vis = &ast.vis;
+    let fns = data.fields.named.iter().map(|field| {
+        let field_name = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let fn_name = format_ident!("__uninit_project_union__{}", field_name);
+        quote! {
+            /// Projects the union to the uninitialized field.
+            ///
+            /// Only one field of a union may be initialized.
+            /// Writing to this field overwrites the bytes of the other fields.
+            #[allow(non_snake_case)]
+            #vis fn #fn_name (this: &mut ::placement_new::__private::core::mem::MaybeUninit<Self>) -> &mut ::placement_new::__private::core::mem::MaybeUninit<#ty> {
+                // every field of a repr(C) union starts at offset 0
+                unsafe { &mut *this.as_mut_ptr().cast() }
+            }
+        }
+    });
+
+    let codegen = quote! {
+        #[doc(hidden)]
+        impl #name {
+            #(#fns)*
+        }
+    };
+
+    codegen.into()
+}
+
 fn clone_and_modify<T: Clone>(origin: &T, f: impl FnOnce(&mut T)) -> T {
     let mut cloned = origin.clone();
     f(&mut cloned);
     cloned
 }
 
-fn project_fields(fields: &Fields) -> Fields {
-    let project_ty = |field: &mut Field| {
+#[derive(Default)]
+struct UninitProjectAttrs {
+    nested: bool,
+    name: Option<Ident>,
+    vis: Option<Visibility>,
+}
+
+/// Parses `#[uninit_project(nested)]` on a field,
+/// or `#[uninit_project(name = Ident, vis = Visibility)]` on a type or an enum variant.
+fn parse_uninit_project_attrs(
+    attrs: &[Attribute],
+    on_field: bool,
+) -> syn::Result<UninitProjectAttrs> {
+    let mut ret = UninitProjectAttrs::default();
+    for attr in attrs {
+        if !attr.path.is_ident("uninit_project") {
+            continue;
+        }
+        attr.parse_args_with(|input: syn::parse::ParseStream| loop {
+            let ident = input.parse::<Ident>()?;
+            if on_field && ident == "nested" {
+                ret.nested = true;
+            } else if !on_field && ident == "name" {
+                let _ = input.parse::<Token![=]>()?;
+                ret.name = Some(input.parse::<Ident>()?);
+            } else if !on_field && ident == "vis" {
+                let _ = input.parse::<Token![=]>()?;
+                ret.vis = Some(input.parse::<Visibility>()?);
+            } else {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    "unknown uninit_project attribute",
+                ));
+            }
+            if input.is_empty() {
+                return Ok(());
+            }
+            let _ = input.parse::<Token![,]>()?;
+        })?;
+    }
+    Ok(ret)
+}
+
+fn strip_attrs(attrs: &mut Vec<Attribute>) {
+    attrs.retain(|attr| !attr.path.is_ident("placement") && !attr.path.is_ident("uninit_project"));
+}
+
+/// Whether `vis` is visible from fewer places than `than`.
+///
+/// Restricted visibilities are assumed to be equal.
+fn is_less_visible(vis: &Visibility, than: &Visibility) -> bool {
+    let rank = |vis: &Visibility| match vis {
+        Visibility::Public(_) => 2,
+        Visibility::Crate(_) | Visibility::Restricted(_) => 1,
+        Visibility::Inherited => 0,
+    };
+    rank(vis) < rank(than)
+}
+
+/// Returns the name of the mirror of an enum variant.
+fn variant_struct_name(name: &Ident, variant: &Variant) -> syn::Result<Ident> {
+    let attrs = parse_uninit_project_attrs(&variant.attrs, false)?;
+    Ok(attrs
+        .name
+        .unwrap_or_else(|| format_ident!("__UninitEnumVariant__{}__{}", name, variant.ident)))
+}
+
+/// Generates the projection of a struct whose last field is a slice.
+#[allow(non_snake_case, clippy::too_many_lines)]
+fn impl_UninitProject_for_dst(ast: &DeriveInput, data: &DataStruct) -> TokenStream {
+    let name = &ast.ident;
+    if !ast.generics.params.is_empty() {
+        emit_error!(
+            &ast.generics,
+            "generic structs with a trailing slice cannot derive UninitProject"
+        )
+    }
+    let attrs = match parse_uninit_project_attrs(&ast.attrs, false) {
+        Ok(attrs) => attrs,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let doc_hidden = if attrs.name.is_some() {
+        quote! {}
+    } else {
+        quote! { #[doc(hidden)] }
+    };
+    let fields_name = attrs
+        .name
+        .unwrap_or_else(|| format_ident!("__UninitDstFields__{}", name));
+    let vis = attrs.vis.as_ref().unwrap_or(&ast.vis);
+
+    let (tail_member, tail) = field_members(&data.fields).last().unwrap();
+    let elem = match tail.ty {
+        Type::Slice(ref slice) => &slice.elem,
+        _ => emit_error!(tail, "only the last field can be a slice"),
+    };
+    let projected_fields = match project_fields(&data.fields) {
+        Ok(fields) => fields,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let sized_fields: Vec<_> = projected_fields
+        .iter()
+        .take(projected_fields.len() - 1)
+        .collect();
+    let mut unsized_fields = data.fields.iter().take(sized_fields.len());
+    if let Some(field) = unsized_fields.find(|field| matches!(field.ty, Type::Slice(_))) {
+        emit_error!(field, "only the last field can be a slice")
+    }
+
+    let decls = sized_fields.iter().map(|field| {
+        let fvis = &field.vis;
+        let ty = &field.ty;
+        let ident = field.ident.iter();
+        quote! { #fvis #(#ident:)* &'__a mut #ty }
+    });
+    let decls = if matches!(data.fields, Fields::Named(_)) {
+        quote! { { #(#decls,)* } }
+    } else {
+        quote! { ( #(#decls,)* ); }
+    };
+    let inits = field_members(&data.fields)
+        .take(sized_fields.len())
+        .map(|(member, _)| {
+            quote! {
+                #member: &mut *::placement_new::__private::core::ptr::addr_of_mut!((*ptr).#member).cast()
+            }
+        });
+
+    // the sized prefix has the same layout as the struct with an empty tail
+    let prefix_name = format_ident!("__UninitDstPrefix__{}", name);
+    let prefix_struct = clone_and_modify(ast, |new_ast| {
+        new_ast.ident = prefix_name.clone();
+        new_ast.attrs.retain(|attr| attr.path.is_ident("repr"));
+        new_ast.vis = Visibility::Inherited;
+        if let Data::Struct(ref mut data) = new_ast.data {
+            let tail = data.fields.iter_mut().last().unwrap();
+            tail.ty = Type::Verbatim(quote! { [#elem; 0] });
+            for field in &mut data.fields {
+                strip_attrs(&mut field.attrs);
+            }
+        }
+    });
+
+    // the offset of the tail cannot be checked, because `offset_of!` needs a sized field
+    let layout_checks = field_members(&data.fields).zip(&sized_fields).map(
+        |((member, field), projected)| {
+            let member_name = match member {
+                Member::Named(ref ident) => ident.to_string(),
+                Member::Unnamed(ref index) => index.index.to_string(),
+            };
+            let offset_msg = format!(
+                "the offset of field `{member_name}` in the prefix of `{name}` is different"
+            );
+            let size_msg =
+                format!("the size of field `{member_name}` in the mirror of `{name}` is different");
+            let (ty, projected_ty) = (&field.ty, &projected.ty);
+            quote! {
+                ::placement_new::__private::core::assert!(
+                    ::placement_new::__private::core::mem::offset_of!(#name, #member)
+                        == ::placement_new::__private::core::mem::offset_of!(#prefix_name, #member),
+                    #offset_msg
+                );
+                ::placement_new::__private::core::assert!(
+                    ::placement_new::__private::core::mem::size_of::<#ty>()
+                        == ::placement_new::__private::core::mem::size_of::<#projected_ty>(),
+                    #size_msg
+                );
+            }
+        },
+    );
+
+    let codegen = quote! {
+        #doc_hidden
+        #[allow(non_camel_case_types)]
+        #vis struct #fields_name<'__a> #decls
+
+        const _: () = {
+            #[allow(dead_code, non_camel_case_types)]
+            #prefix_struct
+
+            const _: () = { #(#layout_checks)* };
+
+            unsafe impl ::placement_new::PlaceTarget for #name {
+                type Metadata = usize;
+
+                fn layout(len: usize) -> ::placement_new::__private::core::alloc::Layout {
+                    let offset = ::placement_new::__private::core::mem::offset_of!(#prefix_name, #tail_member);
+                    let size = ::placement_new::__private::core::mem::size_of::<#elem>()
+                        .checked_mul(len)
+                        .and_then(|size| size.checked_add(offset))
+                        .expect("invalid layout");
+                    let align = ::placement_new::__private::core::mem::align_of::<#prefix_name>();
+                    ::placement_new::__private::core::alloc::Layout::from_size_align(size, align)
+                        .expect("invalid layout")
+                        .pad_to_align()
+                }
+
+                fn from_raw_parts(ptr: *mut (), len: usize) -> *mut Self {
+                    ::placement_new::__private::core::ptr::slice_from_raw_parts_mut(ptr.cast::<#elem>(), len) as *mut Self
+                }
+            }
+
+            unsafe impl ::placement_new::UninitProjectDst for #name {
+                type Fields<'__a> = #fields_name<'__a>;
+
+                type Elem = #elem;
+
+                unsafe fn uninit_project_dst<'__a>(
+                    ptr: *mut Self,
+                ) -> (
+                    #fields_name<'__a>,
+                    &'__a mut [::placement_new::__private::core::mem::MaybeUninit<#elem>],
+                ) {
+                    let tail = ::placement_new::__private::core::ptr::addr_of_mut!((*ptr).#tail_member);
+                    let tail = &mut *(tail as *mut [::placement_new::__private::core::mem::MaybeUninit<#elem>]);
+                    (#fields_name { #(#inits,)* }, tail)
+                }
+            }
+        };
+    };
+
+    codegen.into()
+}
+
+/// Replaces the field types with `MaybeUninit<_>`, or with the mirrors of them for nested fields.
+fn project_fields(fields: &Fields) -> syn::Result<Fields> {
+    let project_ty = |field: &mut Field| -> syn::Result<()> {
+        let nested = parse_uninit_project_attrs(&field.attrs, true)?.nested;
+        strip_attrs(&mut field.attrs);
         let ty = &field.ty;
-        field.ty =
-            Type::Verbatim(quote! { ::placement_new::__private::core::mem::MaybeUninit<#ty> });
+        field.ty = if nested {
+            Type::Verbatim(quote! { <#ty as ::placement_new::UninitProjectExt>::Uninit })
+        } else {
+            Type::Verbatim(quote! { ::placement_new::__private::core::mem::MaybeUninit<#ty> })
+        };
+        Ok(())
     };
 
-    clone_and_modify(fields, |new_fields| match new_fields {
-        Fields::Named(ref mut fields) => fields.named.iter_mut().for_each(project_ty),
-        Fields::Unnamed(ref mut fields) => fields.unnamed.iter_mut().for_each(project_ty),
+    let mut new_fields = fields.clone();
+    match new_fields {
+        Fields::Named(ref mut fields) => fields.named.iter_mut().try_for_each(project_ty)?,
+        Fields::Unnamed(ref mut fields) => fields.unnamed.iter_mut().try_for_each(project_ty)?,
         Fields::Unit => {}
-    })
+    }
+    Ok(new_fields)
+}
+
+/// Generates const assertions which check that `mirror` has the same size, alignment
+/// and field offsets as `origin`, whose fields are `fields`.
+fn layout_checks(
+    origin: &TokenStream2,
+    mirror: &TokenStream2,
+    fields: &Fields,
+    what: &str,
+) -> TokenStream2 {
+    let size_msg = format!("the size of the mirror of {what} is different");
+    let align_msg = format!("the alignment of the mirror of {what} is different");
+    let offset_checks = field_members(fields).map(|(member, _)| {
+        let field_name = match member {
+            Member::Named(ref ident) => ident.to_string(),
+            Member::Unnamed(ref index) => index.index.to_string(),
+        };
+        let msg =
+            format!("the offset of field `{field_name}` in the mirror of {what} is different");
+        quote! {
+            ::placement_new::__private::core::assert!(
+                ::placement_new::__private::core::mem::offset_of!(#origin, #member)
+                    == ::placement_new::__private::core::mem::offset_of!(#mirror, #member),
+                #msg
+            );
+        }
+    });
+    quote! {
+        ::placement_new::__private::core::assert!(
+            ::placement_new::__private::core::mem::size_of::<#origin>()
+                == ::placement_new::__private::core::mem::size_of::<#mirror>(),
+            #size_msg
+        );
+        ::placement_new::__private::core::assert!(
+            ::placement_new::__private::core::mem::align_of::<#origin>()
+                == ::placement_new::__private::core::mem::align_of::<#mirror>(),
+            #align_msg
+        );
+        #(#offset_checks)*
+    }
 }
 
 #[allow(non_snake_case)]
 fn impl_UninitProject_for_struct(ast: &DeriveInput) -> TokenStream {
     let name = &ast.ident;
-    let uninit_name = format_ident!("__UninitStruct__{}", name);
+    if let Data::Struct(ref data) = ast.data {
+        if data
+            .fields
+            .iter()
+            .any(|field| matches!(field.ty, Type::Slice(_)))
+        {
+            return impl_UninitProject_for_dst(ast, data);
+        }
+    }
+    let attrs = match parse_uninit_project_attrs(&ast.attrs, false) {
+        Ok(attrs) => attrs,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let doc_hidden = if attrs.name.is_some() {
+        quote! {}
+    } else {
+        quote! { #[doc(hidden)] }
+    };
+    let UninitProjectAttrs {
+        name: uninit_name,
+        vis,
+        ..
+    } = attrs;
+    let uninit_name = uninit_name.unwrap_or_else(|| format_ident!("__UninitStruct__{}", name));
 
     let data = match ast.data {
         Data::Struct(ref data) => data,
@@ -89,30 +449,122 @@ fn impl_UninitProject_for_struct(ast: &DeriveInput) -> TokenStream {
         emit_error!(ast, "unexpected unit struct")
     }
 
+    let projected_fields = match project_fields(&data.fields) {
+        Ok(fields) => fields,
+        Err(err) => return err.to_compile_error().into(),
+    };
     let projected_struct = clone_and_modify(ast, |new_ast| {
         new_ast.ident = uninit_name.clone();
+        strip_attrs(&mut new_ast.attrs);
+        if let Some(vis) = vis {
+            new_ast.vis = vis;
+        }
         match new_ast.data {
-            Data::Struct(DataStruct { ref mut fields, .. }) => *fields = project_fields(fields),
+            Data::Struct(DataStruct { ref mut fields, .. }) => *fields = projected_fields,
             _ => unreachable!(),
         }
     });
 
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let layout_checks = layout_checks(
+        &quote! { #name #ty_generics },
+        &quote! { #uninit_name #ty_generics },
+        &data.fields,
+        &format!("`{name}`"),
+    );
+    // the layout of a generic struct can only be checked after monomorphization
+    let (layout_checks, generic_layout_checks) = if ast.generics.params.is_empty() {
+        (quote! { const _: () = { #layout_checks }; }, quote! {})
+    } else {
+        (quote! {}, quote! { const { #layout_checks } })
+    };
+
+    // the mirror cannot leak through an associated type
+    let project_ext = if is_less_visible(&projected_struct.vis, &ast.vis) {
+        quote! {}
+    } else {
+        quote! {
+            impl #impl_generics ::placement_new::UninitProjectExt for #name #ty_generics #where_clause {
+                type Uninit = #uninit_name #ty_generics;
+            }
+        }
+    };
+
     let codegen = quote! {
-        #[doc(hidden)]
+        #doc_hidden
         #projected_struct
 
+        #layout_checks
+
         #[doc(hidden)]
-        unsafe impl ::placement_new::UninitProject<#uninit_name> for #name {
-            fn uninit_project(this: &mut MaybeUninit<Self>) -> &mut #uninit_name {
+        unsafe impl #impl_generics ::placement_new::UninitProject<#uninit_name #ty_generics> for #name #ty_generics #where_clause {
+            fn uninit_project(this: &mut ::placement_new::__private::core::mem::MaybeUninit<Self>) -> &mut #uninit_name #ty_generics {
+                #generic_layout_checks
                 unsafe { &mut *this.as_mut_ptr().cast() }
             }
         }
+
+        #project_ext
+
+        #[doc(hidden)]
+        unsafe impl #impl_generics ::placement_new::__private::UninitField<#name #ty_generics> for #uninit_name #ty_generics #where_clause {
+            fn as_uninit(&mut self) -> &mut ::placement_new::__private::core::mem::MaybeUninit<#name #ty_generics> {
+                unsafe { &mut *(self as *mut Self).cast() }
+            }
+        }
+
+        #[doc(hidden)]
+        impl #impl_generics ::placement_new::__private::UninitProjectStep<#uninit_name #ty_generics> for #uninit_name #ty_generics #where_clause {
+            fn project_step(&mut self) -> &mut Self {
+                self
+            }
+        }
     };
 
     codegen.into()
 }
 
-#[allow(non_snake_case)]
+/// The layout of a repr(C) enum is defined by a repr(C) struct of the tag and a repr(C) union
+/// of repr(C) variant structs, so the mirror of each variant is checked against
+/// such a struct with the original field types.
+fn variant_layout_checks(ast: &DeriveInput, variant_structs: &[ItemStruct]) -> Vec<TokenStream2> {
+    let Data::Enum(ref data) = ast.data else {
+        unreachable!()
+    };
+
+    let mut variant_checks: Vec<_> = Vec::new();
+    for (v, vs) in data.variants.iter().zip(variant_structs) {
+        let layout_name = format_ident!("__Layout__{}", v.ident);
+        let layout_struct = ItemStruct {
+            attrs: Vec::new(),
+            vis: syn::Visibility::Inherited,
+            generics: ast.generics.clone(),
+            ident: layout_name.clone(),
+            struct_token: Struct::default(),
+            fields: clone_and_modify(&v.fields, |fields| {
+                fields.iter_mut().for_each(|field| field.attrs.clear());
+            }),
+            semi_token: vs.semi_token,
+        };
+        let uninit_name = &vs.ident;
+        let checks = layout_checks(
+            &quote! { #layout_name },
+            &quote! { #uninit_name },
+            &v.fields,
+            &format!("variant `{}` of `{}`", v.ident, ast.ident),
+        );
+        variant_checks.push(quote! {
+            #[repr(C)]
+            #[allow(dead_code, non_camel_case_types)]
+            #layout_struct
+
+            #checks
+        });
+    }
+    variant_checks
+}
+
+#[allow(non_snake_case, clippy::too_many_lines)]
 fn impl_UninitProject_for_enum(ast: &DeriveInput) -> TokenStream {
     let name = &ast.ident;
     let discriminant_name = format_ident!("__UninitEnumDiscriminant__{}", name);
@@ -122,27 +574,60 @@ fn impl_UninitProject_for_enum(ast: &DeriveInput) -> TokenStream {
         _ => unreachable!(),
     };
 
+    if !ast.generics.params.is_empty() {
+        emit_error!(&ast.generics, "generic enums cannot derive UninitProject")
+    }
+
+    let enum_attrs = match parse_uninit_project_attrs(&ast.attrs, false) {
+        Ok(UninitProjectAttrs {
+            name: Some(ident), ..
+        }) => emit_error!(
+            ident,
+            "the name of an enum mirror must be given on each variant"
+        ),
+        Ok(attrs) => attrs,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
     let discriminant_enum = clone_and_modify(ast, |new_ast| {
         new_ast.ident = discriminant_name.clone();
+        strip_attrs(&mut new_ast.attrs);
         let new_data = match new_ast.data {
             Data::Enum(ref mut data) => data,
             _ => unreachable!(),
         };
-        new_data
-            .variants
-            .iter_mut()
-            .for_each(|variant| variant.fields = Fields::Unit)
+        new_data.variants.iter_mut().for_each(|variant| {
+            strip_attrs(&mut variant.attrs);
+            variant.fields = Fields::Unit;
+        })
     });
 
     let mut variant_structs: Vec<_> = Vec::new();
+    let mut variant_docs: Vec<_> = Vec::new();
     for v in &data.variants {
+        let (fields, attrs) = match project_fields(&v.fields)
+            .and_then(|fields| Ok((fields, parse_uninit_project_attrs(&v.attrs, false)?)))
+        {
+            Ok(ret) => ret,
+            Err(err) => return err.to_compile_error().into(),
+        };
+        variant_docs.push(if attrs.name.is_some() {
+            quote! {}
+        } else {
+            quote! { #[doc(hidden)] }
+        });
         variant_structs.push(ItemStruct {
-            attrs: v.attrs.clone(),
-            vis: ast.vis.clone(),
+            attrs: clone_and_modify(&v.attrs, strip_attrs),
+            vis: attrs
+                .vis
+                .or_else(|| enum_attrs.vis.clone())
+                .unwrap_or_else(|| ast.vis.clone()),
             generics: ast.generics.clone(),
-            ident: format_ident!("__UninitEnumVariant__{}__{}", name, v.ident),
+            ident: attrs
+                .name
+                .unwrap_or_else(|| format_ident!("__UninitEnumVariant__{}__{}", name, v.ident)),
             struct_token: Struct::default(),
-            fields: project_fields(&v.fields),
+            fields,
             semi_token: match v.fields {
                 Fields::Named(_) => None,
                 Fields::Unnamed(_) | Fields::Unit => Some(Semi::default()),
@@ -150,6 +635,11 @@ fn impl_UninitProject_for_enum(ast: &DeriveInput) -> TokenStream {
         });
     }
 
+    let repr_name = format_ident!("__UninitEnumRepr__{}", name);
+    let payload_name = format_ident!("__UninitEnumPayload__{}", name);
+    let variant_names: Vec<_> = data.variants.iter().map(|v| &v.ident).collect();
+    let variant_struct_names: Vec<_> = variant_structs.iter().map(|vs| &vs.ident).collect();
+
     let mut impls: Vec<_> = Vec::new();
     let mut fns: Vec<_> = Vec::new();
     for (v, vs) in data.variants.iter().zip(variant_structs.iter()) {
@@ -162,10 +652,9 @@ fn impl_UninitProject_for_enum(ast: &DeriveInput) -> TokenStream {
                     type Tag = #discriminant_name;
                     type Payload = #uninit_name;
                     unsafe{
-                        let base = this.as_mut_ptr().cast();
-                        let (tag, payload) = ::placement_new::__private::split_enum::<Tag, Payload>(base);
-                        tag.write(Tag::#variant_name);
-                        &mut *payload
+                        let repr = this.as_mut_ptr().cast::<#repr_name>();
+                        ::placement_new::__private::core::ptr::addr_of_mut!((*repr).tag).write(Tag::#variant_name);
+                        &mut *::placement_new::__private::core::ptr::addr_of_mut!((*repr).payload).cast::<Payload>()
                     }
                 }
             }
@@ -182,16 +671,46 @@ fn impl_UninitProject_for_enum(ast: &DeriveInput) -> TokenStream {
         })
     }
 
+    let variant_checks = variant_layout_checks(ast, &variant_structs);
+    let repr_checks = layout_checks(
+        &quote! { #name },
+        &quote! { #repr_name },
+        &Fields::Unit,
+        &format!("`{name}`"),
+    );
+
     let codegen = quote! {
         #[doc(hidden)]
         #discriminant_enum
 
         #(
             #[repr(C)]
-            #[doc(hidden)]
+            #variant_docs
             #variant_structs
         )*
 
+        #[repr(C)]
+        #[doc(hidden)]
+        #[allow(non_snake_case)]
+        union #payload_name {
+            #(
+                #variant_names: ::placement_new::__private::core::mem::ManuallyDrop<#variant_struct_names>,
+            )*
+        }
+
+        #[repr(C)]
+        #[doc(hidden)]
+        struct #repr_name {
+            tag: #discriminant_name,
+            payload: #payload_name,
+        }
+
+        const _: () = {
+            #repr_checks
+
+            #(#variant_checks)*
+        };
+
         #(#impls)*
 
         #[doc(hidden)]
@@ -203,25 +722,905 @@ fn impl_UninitProject_for_enum(ast: &DeriveInput) -> TokenStream {
     codegen.into()
 }
 
-#[doc(hidden)]
-#[proc_macro]
-pub fn __uninit_project_variant(tt: TokenStream) -> TokenStream {
-    struct ProjectorPath {
-        ty_path: Path,
-        variant: Ident,
+/// Implements `Zeroable` for a struct whose fields are all `Zeroable`.
+#[allow(clippy::missing_panics_doc)]
+#[proc_macro_derive(Zeroable)]
+pub fn zeroable(input: TokenStream) -> TokenStream {
+    impl_Zeroable(&syn::parse(input).unwrap())
+}
+
+#[allow(non_snake_case)]
+fn impl_Zeroable(ast: &DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+
+    let Data::Struct(ref data) = ast.data else {
+        emit_error!(ast, "Only structs can derive Zeroable")
+    };
+
+    let generics = bound_field_types(ast, &data.fields, |_| {
+        Some(quote! { ::placement_new::Zeroable })
+    });
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let codegen = quote! {
+        unsafe impl #impl_generics ::placement_new::Zeroable for #name #ty_generics #where_clause {}
+    };
+
+    codegen.into()
+}
+
+/// Returns the generics of `ast`, where the type of every field in `fields`
+/// is bounded by the trait given by `bound`, if any.
+fn bound_field_types<'a>(
+    ast: &DeriveInput,
+    fields: impl IntoIterator<Item = &'a Field>,
+    bound: impl Fn(&Field) -> Option<TokenStream2>,
+) -> Generics {
+    let mut generics = ast.generics.clone();
+    let where_clause = generics.make_where_clause();
+    for field in fields {
+        if let Some(bound) = bound(field) {
+            let ty = &field.ty;
+            where_clause
+                .predicates
+                .push(syn::parse_quote_spanned!(ty.span()=> #ty: #bound));
+        }
     }
+    generics
+}
 
-    impl Parse for ProjectorPath {
-        fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-            let ty_path = input.parse::<Path>()?;
-            let _ = input.parse::<Token![=>]>()?;
-            let variant = input.parse::<Ident>()?;
-            Ok(Self { ty_path, variant })
+fn field_members(fields: &Fields) -> impl Iterator<Item = (Member, &Field)> {
+    fields.iter().enumerate().map(|(i, field)| {
+        let member = field
+            .ident
+            .clone()
+            .map_or_else(|| Member::Unnamed(Index::from(i)), Member::Named);
+        (member, field)
+    })
+}
+
+#[derive(Default)]
+struct PlacementAttrs {
+    default: Option<Expr>,
+    clone: bool,
+    copy: bool,
+}
+
+fn parse_placement_attrs(field_attrs: &[Attribute]) -> syn::Result<PlacementAttrs> {
+    let mut attrs = PlacementAttrs::default();
+    for attr in field_attrs {
+        if !attr.path.is_ident("placement") {
+            continue;
         }
+        attr.parse_args_with(|input: syn::parse::ParseStream| loop {
+            let ident = input.parse::<Ident>()?;
+            if ident == "default" {
+                let _ = input.parse::<Token![=]>()?;
+                attrs.default = Some(input.parse::<Expr>()?);
+            } else if ident == "clone" {
+                attrs.clone = true;
+            } else if ident == "copy" {
+                attrs.copy = true;
+            } else {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    "unknown placement attribute",
+                ));
+            }
+            if input.is_empty() {
+                return Ok(());
+            }
+            let _ = input.parse::<Token![,]>()?;
+        })?;
+    }
+    Ok(attrs)
+}
+
+/// Returns `&mut MaybeUninit<_>` of a field of the mirror `this`,
+/// whether the field is projected or nested.
+fn field_place(member: &Member) -> TokenStream2 {
+    quote! { ::placement_new::__private::UninitField::as_uninit(&mut this.#member) }
+}
+
+/// Initializes fields of `this` one by one.
+/// If an initializer panics, every field initialized before will be dropped.
+fn init_fields(inits: Vec<(Member, TokenStream2)>) -> TokenStream2 {
+    let guards: Vec<_> = (0..inits.len())
+        .map(|i| format_ident!("__guard{}", i))
+        .collect();
+    let (members, inits): (Vec<_>, Vec<_>) = inits.into_iter().unzip();
+    let places = members.iter().map(field_place);
+    quote! {
+        #(
+            #inits;
+            let #guards = unsafe {
+                ::placement_new::__private::DropGuard::new(#places.as_mut_ptr())
+            };
+        )*
+        ::placement_new::__private::core::mem::forget((#(#guards,)*));
+    }
+}
+
+/// Implements `DefaultInPlace` for a struct which derives `UninitProject`.
+///
+/// Every field is initialized by `DefaultInPlace`,
+/// or by the expression given in `#[placement(default = expr)]`.
+#[allow(clippy::missing_panics_doc)]
+#[proc_macro_derive(DefaultInPlace, attributes(placement))]
+pub fn default_in_place(input: TokenStream) -> TokenStream {
+    impl_DefaultInPlace(&syn::parse(input).unwrap())
+}
+
+#[allow(non_snake_case)]
+fn impl_DefaultInPlace(ast: &DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+
+    let Data::Struct(ref data) = ast.data else {
+        emit_error!(ast, "Only structs can derive DefaultInPlace")
+    };
+
+    let mut inits: Vec<_> = Vec::new();
+    for (member, field) in field_members(&data.fields) {
+        let place = field_place(&member);
+        let init = match parse_placement_attrs(&field.attrs) {
+            Ok(PlacementAttrs {
+                default: Some(expr),
+                ..
+            }) => quote! { ::placement_new::overwrite(#place, #expr) },
+            Ok(_) => quote! { ::placement_new::DefaultInPlace::default_in_place(#place) },
+            Err(err) => return err.to_compile_error().into(),
+        };
+        inits.push((member, init));
     }
+    let init_fields = init_fields(inits);
 
-    let ProjectorPath { ty_path, variant } = syn::parse_macro_input!(tt as ProjectorPath);
-    let fn_name = format_ident!("__uninit_project_variant__{}", variant);
+    let generics = bound_field_types(ast, &data.fields, |field| {
+        match parse_placement_attrs(&field.attrs) {
+            Ok(PlacementAttrs {
+                default: Some(_), ..
+            }) => None,
+            _ => Some(quote! { ::placement_new::DefaultInPlace }),
+        }
+    });
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let codegen = quote! {
+        unsafe impl #impl_generics ::placement_new::DefaultInPlace for #name #ty_generics #where_clause {
+            fn default_in_place(this: &mut ::placement_new::__private::core::mem::MaybeUninit<Self>) {
+                let this = ::placement_new::UninitProject::<_>::uninit_project(this);
+                #init_fields
+            }
+        }
+    };
+
+    codegen.into()
+}
+
+/// Implements `CloneInto` for a struct or an enum which derives `UninitProject`.
+///
+/// Every field is cloned by `CloneInto`,
+/// by `Clone` if it is marked with `#[placement(clone)]`,
+/// or by `copy_into` if it is marked with `#[placement(copy)]`.
+///
+/// A `Copy` type marked with `#[placement(copy)]` is copied as a whole,
+/// without deriving `UninitProject`.
+#[allow(clippy::missing_panics_doc)]
+#[proc_macro_derive(CloneInto, attributes(placement))]
+pub fn clone_into(input: TokenStream) -> TokenStream {
+    impl_CloneInto(&syn::parse(input).unwrap())
+}
+
+fn clone_fields(
+    fields: &Fields,
+    src: impl Fn(&Member) -> TokenStream2,
+) -> syn::Result<TokenStream2> {
+    let mut inits: Vec<_> = Vec::new();
+    for (member, field) in field_members(fields) {
+        let src = src(&member);
+        let place = field_place(&member);
+        let attrs = parse_placement_attrs(&field.attrs)?;
+        let init = if attrs.copy {
+            quote! { ::placement_new::copy_into(#src, #place) }
+        } else if attrs.clone {
+            quote! { ::placement_new::overwrite(#place, ::placement_new::__private::core::clone::Clone::clone(#src)) }
+        } else {
+            quote! { ::placement_new::CloneInto::clone_into_uninit(#src, #place) }
+        };
+        inits.push((member, init));
+    }
+    Ok(init_fields(inits))
+}
+
+#[allow(non_snake_case)]
+fn impl_CloneInto(ast: &DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+
+    match parse_placement_attrs(&ast.attrs) {
+        Ok(PlacementAttrs { copy: true, .. }) => return impl_CloneInto_by_copy(ast),
+        Ok(_) => {}
+        Err(err) => return err.to_compile_error().into(),
+    }
+
+    let body = match ast.data {
+        Data::Struct(ref data) => clone_fields(&data.fields, |member| quote! { &self.#member })
+            .map(|init_fields| {
+                quote! {
+                    let this = ::placement_new::UninitProject::<_>::uninit_project(dst);
+                    #init_fields
+                }
+            }),
+        Data::Enum(ref data) => data
+            .variants
+            .iter()
+            .map(|v| {
+                let variant_name = &v.ident;
+                let fn_name = format_ident!("__uninit_project_variant__{}", variant_name);
+                let bind = |member: &Member| match *member {
+                    Member::Named(ref ident) => format_ident!("__self_{}", ident),
+                    Member::Unnamed(ref index) => format_ident!("__self_{}", index.index),
+                };
+                let members: Vec<_> = field_members(&v.fields).map(|(m, _)| m).collect();
+                let binds: Vec<_> = members.iter().map(bind).collect();
+                let init_fields = clone_fields(&v.fields, |member| {
+                    let bind = bind(member);
+                    quote! { #bind }
+                })?;
+                Ok(quote! {
+                    Self::#variant_name { #(#members: ref #binds),* } => {
+                        #[allow(unused_variables)]
+                        let this = Self::#fn_name(dst);
+                        #init_fields
+                    }
+                })
+            })
+            .collect::<syn::Result<Vec<_>>>()
+            .map(|arms| quote! { match *self { #(#arms)* } }),
+        Data::Union(_) => emit_error!(ast, "Only structs and enums can derive CloneInto"),
+    };
+
+    let body = match body {
+        Ok(body) => body,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let fields: Vec<&Field> = match ast.data {
+        Data::Struct(ref data) => data.fields.iter().collect(),
+        Data::Enum(ref data) => data.variants.iter().flat_map(|v| &v.fields).collect(),
+        Data::Union(_) => unreachable!(),
+    };
+    let generics = bound_field_types(ast, fields, |field| {
+        match parse_placement_attrs(&field.attrs) {
+            Ok(PlacementAttrs { copy: true, .. }) => {
+                Some(quote! { ::placement_new::__private::core::marker::Copy })
+            }
+            Ok(PlacementAttrs { clone: true, .. }) => {
+                Some(quote! { ::placement_new::__private::core::clone::Clone })
+            }
+            _ => Some(quote! { ::placement_new::CloneInto }),
+        }
+    });
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let codegen = quote! {
+        unsafe impl #impl_generics ::placement_new::CloneInto for #name #ty_generics #where_clause {
+            fn clone_into_uninit(&self, dst: &mut ::placement_new::__private::core::mem::MaybeUninit<Self>) {
+                #body
+            }
+        }
+    };
+
+    codegen.into()
+}
+
+/// Implements `CloneInto` for a `Copy` type marked with `#[placement(copy)]`.
+#[allow(non_snake_case)]
+fn impl_CloneInto_by_copy(ast: &DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+
+    let mut generics = ast.generics.clone();
+    let (_, ty_generics, _) = ast.generics.split_for_impl();
+    generics.make_where_clause().predicates.push(
+        syn::parse_quote! { #name #ty_generics: ::placement_new::__private::core::marker::Copy },
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let codegen = quote! {
+        unsafe impl #impl_generics ::placement_new::CloneInto for #name #ty_generics #where_clause {
+            #[inline]
+            fn clone_into_uninit(&self, dst: &mut ::placement_new::__private::core::mem::MaybeUninit<Self>) {
+                ::placement_new::copy_into(self, dst)
+            }
+        }
+    };
+
+    codegen.into()
+}
+
+/// Implements `DeserializeInPlace` for a struct which derives `UninitProject`.
+///
+/// Every field is deserialized by `DeserializeInPlace` directly into the uninitialized struct.
+/// A missing field is initialized by the expression given in `#[placement(default = expr)]`,
+/// or causes an error otherwise.
+#[allow(clippy::missing_panics_doc)]
+#[proc_macro_derive(DeserializeInPlace, attributes(placement))]
+pub fn deserialize_in_place(input: TokenStream) -> TokenStream {
+    impl_DeserializeInPlace(&syn::parse(input).unwrap())
+}
+
+#[allow(non_snake_case, clippy::too_many_lines)]
+fn impl_DeserializeInPlace(ast: &DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+    let name_str = name.to_string();
+
+    let Data::Struct(ref data) = ast.data else {
+        emit_error!(ast, "Only structs can derive DeserializeInPlace")
+    };
+
+    if matches!(data.fields, Fields::Unit) {
+        emit_error!(ast, "unexpected unit struct")
+    }
+
+    let mut members: Vec<_> = Vec::new();
+    let mut defaults: Vec<_> = Vec::new();
+    for (member, field) in field_members(&data.fields) {
+        match parse_placement_attrs(&field.attrs) {
+            Ok(attrs) => defaults.push(attrs.default),
+            Err(err) => return err.to_compile_error().into(),
+        }
+        members.push(member);
+    }
+
+    let len = members.len();
+    let places: Vec<_> = members.iter().map(field_place).collect();
+    let guards: Vec<_> = (0..len).map(|i| format_ident!("__guard{}", i)).collect();
+    let expecting = format!("struct {name_str}");
+
+    let seq_inits = places.iter().zip(&defaults).enumerate().map(|(i, (place, default))| {
+        let on_missing = default.as_ref().map_or_else(
+            || quote! {
+                return Err(::placement_new::__private::serde::de::Error::invalid_length(#i, &#expecting))
+            },
+            |expr| quote! { ::placement_new::overwrite(#place, #expr) },
+        );
+        quote! {
+            if __seq.next_element_seed(::placement_new::UninitSeed(#place))?.is_none() {
+                #on_missing;
+            }
+        }
+    });
+
+    let visit_seq = quote! {
+        fn visit_seq<__A>(self, mut __seq: __A) -> ::placement_new::__private::core::result::Result<(), __A::Error>
+        where
+            __A: ::placement_new::__private::serde::de::SeqAccess<'de>,
+        {
+            let this = ::placement_new::UninitProject::<_>::uninit_project(self.0);
+            #(
+                #seq_inits
+                let #guards = unsafe {
+                    ::placement_new::__private::DropGuard::new(#places.as_mut_ptr())
+                };
+            )*
+            ::placement_new::__private::core::mem::forget((#(#guards,)*));
+            *self.1 = true;
+            Ok(())
+        }
+    };
+
+    let (visit_map, deserialize) = match data.fields {
+        Fields::Named(_) => {
+            let field_names: Vec<_> = members
+                .iter()
+                .map(|member| match *member {
+                    Member::Named(ref ident) => {
+                        let s = ident.to_string();
+                        s.strip_prefix("r#")
+                            .map_or_else(|| s.clone(), str::to_owned)
+                    }
+                    Member::Unnamed(_) => unreachable!(),
+                })
+                .collect();
+            let field_bytes: Vec<_> = field_names
+                .iter()
+                .map(|s| syn::LitByteStr::new(s.as_bytes(), proc_macro2::Span::call_site()))
+                .collect();
+            let variants: Vec<_> = (0..len).map(|i| format_ident!("__field{}", i)).collect();
+            let indices: Vec<_> = (0..len as u64).collect();
+
+            let on_missing = places.iter().zip(&defaults).zip(&field_names).zip(&guards).map(
+                |(((place, default), field_name), guard)| {
+                    default.as_ref().map_or_else(
+                        || quote! {
+                            return Err(<__A::Error as ::placement_new::__private::serde::de::Error>::missing_field(#field_name));
+                        },
+                        |expr| quote! {
+                            ::placement_new::overwrite(#place, #expr);
+                            #guard = Some(unsafe {
+                                ::placement_new::__private::DropGuard::new(#place.as_mut_ptr())
+                            });
+                        },
+                    )
+                },
+            );
+
+            let visit_map = quote! {
+                fn visit_map<__A>(self, mut __map: __A) -> ::placement_new::__private::core::result::Result<(), __A::Error>
+                where
+                    __A: ::placement_new::__private::serde::de::MapAccess<'de>,
+                {
+                    #[allow(non_camel_case_types)]
+                    enum __Field {
+                        #(#variants,)*
+                        __ignore,
+                    }
+
+                    struct __FieldVisitor;
+
+                    impl<'de> ::placement_new::__private::serde::de::Visitor<'de> for __FieldVisitor {
+                        type Value = __Field;
+
+                        fn expecting(&self, f: &mut ::placement_new::__private::core::fmt::Formatter<'_>) -> ::placement_new::__private::core::fmt::Result {
+                            f.write_str("field identifier")
+                        }
+
+                        fn visit_u64<__E>(self, v: u64) -> ::placement_new::__private::core::result::Result<__Field, __E> {
+                            match v {
+                                #(#indices => Ok(__Field::#variants),)*
+                                _ => Ok(__Field::__ignore),
+                            }
+                        }
+
+                        fn visit_str<__E>(self, v: &str) -> ::placement_new::__private::core::result::Result<__Field, __E> {
+                            match v {
+                                #(#field_names => Ok(__Field::#variants),)*
+                                _ => Ok(__Field::__ignore),
+                            }
+                        }
+
+                        fn visit_bytes<__E>(self, v: &[u8]) -> ::placement_new::__private::core::result::Result<__Field, __E> {
+                            match v {
+                                #(#field_bytes => Ok(__Field::#variants),)*
+                                _ => Ok(__Field::__ignore),
+                            }
+                        }
+                    }
+
+                    impl<'de> ::placement_new::__private::serde::Deserialize<'de> for __Field {
+                        fn deserialize<__D>(deserializer: __D) -> ::placement_new::__private::core::result::Result<Self, __D::Error>
+                        where
+                            __D: ::placement_new::__private::serde::Deserializer<'de>,
+                        {
+                            deserializer.deserialize_identifier(__FieldVisitor)
+                        }
+                    }
+
+                    let this = ::placement_new::UninitProject::<_>::uninit_project(self.0);
+                    #(
+                        let mut #guards = None;
+                    )*
+                    while let Some(__key) = __map.next_key::<__Field>()? {
+                        match __key {
+                            #(
+                                __Field::#variants => {
+                                    if #guards.is_some() {
+                                        return Err(<__A::Error as ::placement_new::__private::serde::de::Error>::duplicate_field(#field_names));
+                                    }
+                                    __map.next_value_seed(::placement_new::UninitSeed(#places))?;
+                                    #guards = Some(unsafe {
+                                        ::placement_new::__private::DropGuard::new(#places.as_mut_ptr())
+                                    });
+                                }
+                            )*
+                            __Field::__ignore => {
+                                let _ = __map.next_value::<::placement_new::__private::serde::de::IgnoredAny>()?;
+                            }
+                        }
+                    }
+                    #(
+                        if #guards.is_none() {
+                            #on_missing
+                        }
+                    )*
+                    ::placement_new::__private::core::mem::forget((#(#guards,)*));
+                    *self.1 = true;
+                    Ok(())
+                }
+            };
+            let deserialize = quote! {
+                const FIELDS: &[&str] = &[#(#field_names),*];
+                ::placement_new::__private::deserialize_visited(this, |this, visited| {
+                    deserializer.deserialize_struct(#name_str, FIELDS, __Visitor(this, visited))
+                })
+            };
+            (visit_map, deserialize)
+        }
+        // a newtype struct is deserialized as its field, like serde's derive does
+        Fields::Unnamed(_) if len == 1 => {
+            let place = &places[0];
+            let visit_newtype_struct = quote! {
+                fn visit_newtype_struct<__E>(self, __deserializer: __E) -> ::placement_new::__private::core::result::Result<(), __E::Error>
+                where
+                    __E: ::placement_new::__private::serde::Deserializer<'de>,
+                {
+                    let this = ::placement_new::UninitProject::<_>::uninit_project(self.0);
+                    ::placement_new::DeserializeInPlace::deserialize_in_place(__deserializer, #place)?;
+                    *self.1 = true;
+                    Ok(())
+                }
+            };
+            let deserialize = quote! {
+                ::placement_new::__private::deserialize_visited(this, |this, visited| {
+                    deserializer.deserialize_newtype_struct(#name_str, __Visitor(this, visited))
+                })
+            };
+            (visit_newtype_struct, deserialize)
+        }
+        Fields::Unnamed(_) => {
+            let deserialize = quote! {
+                ::placement_new::__private::deserialize_visited(this, |this, visited| {
+                    deserializer.deserialize_tuple_struct(#name_str, #len, __Visitor(this, visited))
+                })
+            };
+            (quote! {}, deserialize)
+        }
+        Fields::Unit => unreachable!(),
+    };
+
+    let mut generics = bound_field_types(ast, &data.fields, |_| {
+        Some(quote! { ::placement_new::DeserializeInPlace<'de> })
+    });
+    generics.params.insert(0, syn::parse_quote!('de));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = ast.generics.split_for_impl();
+
+    // the visitor is a local item, which declares its own copy of the generics
+    let mut visitor_generics = ast.generics.clone();
+    visitor_generics.params.insert(0, syn::parse_quote!('__a));
+    let (visitor_decl_generics, visitor_ty_generics, visitor_where_clause) =
+        visitor_generics.split_for_impl();
+    let mut visitor_impl_generics = generics.clone();
+    visitor_impl_generics
+        .params
+        .insert(1, syn::parse_quote!('__a));
+    let (visitor_impl_generics, _, _) = visitor_impl_generics.split_for_impl();
+
+    let codegen = quote! {
+        unsafe impl #impl_generics ::placement_new::DeserializeInPlace<'de> for #name #ty_generics #where_clause {
+            fn deserialize_in_place<__D>(
+                deserializer: __D,
+                this: &mut ::placement_new::__private::core::mem::MaybeUninit<Self>,
+            ) -> ::placement_new::__private::core::result::Result<(), __D::Error>
+            where
+                __D: ::placement_new::__private::serde::Deserializer<'de>,
+            {
+                // the flag is set once the visitor has initialized the struct
+                struct __Visitor #visitor_decl_generics (
+                    &'__a mut ::placement_new::__private::core::mem::MaybeUninit<#name #ty_generics>,
+                    &'__a mut bool,
+                ) #visitor_where_clause;
+
+                impl #visitor_impl_generics ::placement_new::__private::serde::de::Visitor<'de> for __Visitor #visitor_ty_generics #where_clause {
+                    type Value = ();
+
+                    fn expecting(&self, f: &mut ::placement_new::__private::core::fmt::Formatter<'_>) -> ::placement_new::__private::core::fmt::Result {
+                        f.write_str(#expecting)
+                    }
+
+                    #visit_seq
+
+                    #visit_map
+                }
+
+                #deserialize
+            }
+        }
+    };
+
+    codegen.into()
+}
+
+/// Implements `FromBytesInPlace` for a struct or an enum which derives `UninitProject`.
+///
+/// Every field is read by `FromBytesInPlace` at its offset in the mirror.
+/// The discriminant of an enum is validated against its variants.
+#[allow(clippy::missing_panics_doc)]
+#[proc_macro_derive(FromBytesInPlace)]
+pub fn from_bytes_in_place(input: TokenStream) -> TokenStream {
+    impl_FromBytesInPlace(&syn::parse(input).unwrap())
+}
+
+fn from_bytes_fields(fields: &Fields) -> TokenStream2 {
+    let inits = field_members(fields)
+        .map(|(member, _)| {
+            let place = field_place(&member);
+            let init = quote! {{
+                let __place = #place;
+                let __offset = __place.as_ptr() as usize - __base;
+                ::placement_new::FromBytesInPlace::from_bytes_in_place(
+                    bytes.get(__offset..).unwrap_or(&[]),
+                    __place,
+                )
+                .map_err(|e| e.add_offset(__offset))?
+            }};
+            (member, init)
+        })
+        .collect();
+    init_fields(inits)
+}
+
+#[allow(non_snake_case)]
+fn impl_FromBytesInPlace(ast: &DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+
+    let body = match ast.data {
+        Data::Struct(ref data) => {
+            let init_fields = from_bytes_fields(&data.fields);
+            quote! {
+                let this = ::placement_new::UninitProject::<_>::uninit_project(this);
+                #init_fields
+                Ok(())
+            }
+        }
+        Data::Enum(ref data) => {
+            let discriminant_name = format_ident!("__UninitEnumDiscriminant__{}", name);
+            let arms = data.variants.iter().map(|v| {
+                let variant_name = &v.ident;
+                let fn_name = format_ident!("__uninit_project_variant__{}", variant_name);
+                let init_fields = from_bytes_fields(&v.fields);
+                quote! {
+                    let __variant = Tag::#variant_name;
+                    let __variant = unsafe {
+                        ::placement_new::__private::core::slice::from_raw_parts(
+                            ::placement_new::__private::core::ptr::addr_of!(__variant).cast::<u8>(),
+                            ::placement_new::__private::core::mem::size_of::<Tag>(),
+                        )
+                    };
+                    if __tag == __variant {
+                        #[allow(unused_variables)]
+                        let this = Self::#fn_name(this);
+                        #init_fields
+                        return Ok(());
+                    }
+                }
+            });
+            quote! {
+                type Tag = #discriminant_name;
+                let __tag = bytes
+                    .get(..::placement_new::__private::core::mem::size_of::<Tag>())
+                    .ok_or(::placement_new::FromBytesError::new(0, ::placement_new::FromBytesErrorKind::UnexpectedEnd))?;
+                #(#arms)*
+                Err(::placement_new::FromBytesError::new(0, ::placement_new::FromBytesErrorKind::InvalidDiscriminant))
+            }
+        }
+        Data::Union(_) => emit_error!(ast, "Only structs and enums can derive FromBytesInPlace"),
+    };
+
+    let fields: Vec<&Field> = match ast.data {
+        Data::Struct(ref data) => data.fields.iter().collect(),
+        Data::Enum(ref data) => data.variants.iter().flat_map(|v| &v.fields).collect(),
+        Data::Union(_) => unreachable!(),
+    };
+    let generics = bound_field_types(ast, fields, |_| {
+        Some(quote! { ::placement_new::FromBytesInPlace })
+    });
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let codegen = quote! {
+        unsafe impl #impl_generics ::placement_new::FromBytesInPlace for #name #ty_generics #where_clause {
+            fn from_bytes_in_place(
+                bytes: &[u8],
+                this: &mut ::placement_new::__private::core::mem::MaybeUninit<Self>,
+            ) -> ::placement_new::__private::core::result::Result<(), ::placement_new::FromBytesError> {
+                #[allow(unused_variables)]
+                let __base = this.as_ptr() as usize;
+                #body
+            }
+        }
+    };
+
+    codegen.into()
+}
+
+/// Implements `LayoutInfo` for a struct or an enum which derives `UninitProject`.
+#[allow(clippy::missing_panics_doc)]
+#[proc_macro_derive(LayoutInfo)]
+pub fn layout_info(input: TokenStream) -> TokenStream {
+    impl_LayoutInfo(&syn::parse(input).unwrap())
+}
+
+/// Generates a `FieldInfo` table of `fields` in `container`,
+/// whose offsets are moved forward by `base`.
+fn field_infos(fields: &Fields, container: &TokenStream2, base: &TokenStream2) -> TokenStream2 {
+    let infos = field_members(fields).map(|(member, field)| {
+        let name = match member {
+            Member::Named(ref ident) => ident.to_string(),
+            Member::Unnamed(ref index) => index.index.to_string(),
+        };
+        let ty = &field.ty;
+        quote! {
+            ::placement_new::FieldInfo {
+                name: #name,
+                offset: #base + ::placement_new::__private::core::mem::offset_of!(#container, #member),
+                size: ::placement_new::__private::core::mem::size_of::<#ty>(),
+                align: ::placement_new::__private::core::mem::align_of::<#ty>(),
+                type_name: ::placement_new::__private::core::stringify!(#ty),
+            }
+        }
+    });
+    quote! { &[#(#infos),*] }
+}
+
+#[allow(non_snake_case)]
+fn impl_LayoutInfo(ast: &DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+
+    let body = match ast.data {
+        Data::Struct(ref data) => {
+            if let Some(field) = data
+                .fields
+                .iter()
+                .find(|field| matches!(field.ty, Type::Slice(_)))
+            {
+                emit_error!(
+                    field,
+                    "structs with a trailing slice cannot derive LayoutInfo"
+                )
+            }
+            let layout = field_infos(&data.fields, &quote! { Self }, &quote! { 0 });
+            quote! {
+                const LAYOUT: &'static [::placement_new::FieldInfo] = #layout;
+            }
+        }
+        Data::Enum(ref data) => {
+            // the layout of an enum is read from its mirrors, which are not generic
+            if !ast.generics.params.is_empty() {
+                emit_error!(&ast.generics, "generic enums cannot derive LayoutInfo")
+            }
+            let discriminant_name = format_ident!("__UninitEnumDiscriminant__{}", name);
+            let repr_name = format_ident!("__UninitEnumRepr__{}", name);
+            let mut variants: Vec<_> = Vec::new();
+            for v in &data.variants {
+                let variant_name = v.ident.to_string();
+                let variant_struct = match variant_struct_name(name, v) {
+                    Ok(ident) => ident,
+                    Err(err) => return err.to_compile_error().into(),
+                };
+                let fields = field_infos(
+                    &v.fields,
+                    &quote! { #variant_struct },
+                    &quote! { ::placement_new::__private::core::mem::offset_of!(#repr_name, payload) },
+                );
+                variants.push(quote! {
+                    ::placement_new::VariantInfo {
+                        name: #variant_name,
+                        fields: #fields,
+                    }
+                });
+            }
+            quote! {
+                const LAYOUT: &'static [::placement_new::FieldInfo] = &[];
+
+                const VARIANTS: &'static [::placement_new::VariantInfo] = &[#(#variants),*];
+
+                const TAG: ::placement_new::__private::core::option::Option<::placement_new::TagInfo> =
+                    ::placement_new::__private::core::option::Option::Some(::placement_new::TagInfo {
+                        offset: ::placement_new::__private::core::mem::offset_of!(#repr_name, tag),
+                        size: ::placement_new::__private::core::mem::size_of::<#discriminant_name>(),
+                    });
+            }
+        }
+        Data::Union(_) => emit_error!(ast, "Only structs and enums can derive LayoutInfo"),
+    };
+
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    let codegen = quote! {
+        impl #impl_generics ::placement_new::LayoutInfo for #name #ty_generics #where_clause {
+            #body
+        }
+    };
+
+    codegen.into()
+}
+
+/// Implements `UninitPtrProject` for a struct, including `repr(packed)` structs.
+#[allow(clippy::missing_panics_doc)]
+#[proc_macro_derive(UninitPtrProject)]
+pub fn uninit_ptr_project(input: TokenStream) -> TokenStream {
+    impl_UninitPtrProject(&syn::parse(input).unwrap())
+}
+
+#[allow(non_snake_case)]
+fn impl_UninitPtrProject(ast: &DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+    let fields_name = format_ident!("__UninitPtrFields__{}", name);
+
+    let Data::Struct(ref data) = ast.data else {
+        emit_error!(ast, "Only structs can derive UninitPtrProject")
+    };
+
+    if matches!(data.fields, Fields::Unit) {
+        emit_error!(ast, "unexpected unit struct")
+    }
+
+    let decls = data.fields.iter().map(|field| {
+        let vis = &field.vis;
+        let ty = &field.ty;
+        let ident = field.ident.iter();
+        quote! { #vis #(#ident:)* ::placement_new::UninitPtr<'__a, #ty> }
+    });
+    let mut generics = ast.generics.clone();
+    generics.params.insert(0, syn::parse_quote!('__a));
+    let (_, ty_generics, _) = ast.generics.split_for_impl();
+    generics
+        .make_where_clause()
+        .predicates
+        .push(syn::parse_quote! { #name #ty_generics: '__a });
+    let (impl_generics, fields_ty_generics, where_clause) = generics.split_for_impl();
+
+    let decls = if matches!(data.fields, Fields::Named(_)) {
+        quote! { #where_clause { #(#decls,)* } }
+    } else {
+        quote! { ( #(#decls,)* ) #where_clause; }
+    };
+
+    let inits = field_members(&data.fields).map(|(member, _)| {
+        quote! {
+            #member: ::placement_new::UninitPtr::from_raw(
+                ::placement_new::__private::core::ptr::addr_of_mut!((*ptr).#member)
+            )
+        }
+    });
+
+    let vis = &ast.vis;
+    let codegen = quote! {
+        #[doc(hidden)]
+        #[allow(non_camel_case_types)]
+        #vis struct #fields_name #impl_generics #decls
+
+        unsafe impl #impl_generics ::placement_new::UninitPtrProject<'__a> for #name #ty_generics #where_clause {
+            type Fields = #fields_name #fields_ty_generics;
+
+            #[inline]
+            fn project_fields(this: ::placement_new::UninitPtr<'__a, Self>) -> Self::Fields {
+                let ptr = this.as_ptr();
+                // the fields are projected without creating references
+                unsafe { #fields_name { #(#inits,)* } }
+            }
+        }
+    };
+
+    codegen.into()
+}
+
+struct ProjectorPath {
+    ty_path: Path,
+    member: Ident,
+}
+
+impl Parse for ProjectorPath {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let ty_path = input.parse::<Path>()?;
+        let _ = input.parse::<Token![=>]>()?;
+        let member = input.parse::<Ident>()?;
+        Ok(Self { ty_path, member })
+    }
+}
+
+#[doc(hidden)]
+#[proc_macro]
+pub fn __uninit_project_variant(tt: TokenStream) -> TokenStream {
+    let ProjectorPath { ty_path, member } = syn::parse_macro_input!(tt as ProjectorPath);
+    let fn_name = format_ident!("__uninit_project_variant__{}", member);
+    let codegen = quote! { #ty_path :: #fn_name };
+    codegen.into()
+}
+
+#[doc(hidden)]
+#[proc_macro]
+pub fn __uninit_project_union(tt: TokenStream) -> TokenStream {
+    let ProjectorPath { ty_path, member } = syn::parse_macro_input!(tt as ProjectorPath);
+    let fn_name = format_ident!("__uninit_project_union__{}", member);
     let codegen = quote! { #ty_path :: #fn_name };
     codegen.into()
 }
@@ -2,14 +2,69 @@
 
 pub use core;
 
-#[allow(clippy::missing_const_for_fn)] // FIXME: false positive
-#[inline]
-pub unsafe fn split_enum<T, P>(base: *mut ()) -> (*mut T, *mut P) {
-    let base = base.cast::<u8>();
-    let tag = base.cast::<T>();
-    let payload = base.add(core::mem::size_of::<T>()).cast::<P>();
-    (tag, payload)
+#[cfg(feature = "serde")]
+pub use serde;
+
+use crate::UninitProject;
+
+use core::mem::MaybeUninit;
+
+/// A field of a mirror, which is either `MaybeUninit<T>` or the nested mirror of `T`.
+pub unsafe trait UninitField<T> {
+    fn as_uninit(&mut self) -> &mut MaybeUninit<T>;
+}
+
+unsafe impl<T> UninitField<T> for MaybeUninit<T> {
+    #[inline]
+    fn as_uninit(&mut self) -> &mut Self {
+        self
+    }
+}
+
+/// One step of a deep projection, from a field of a mirror to the mirror of the field.
+pub trait UninitProjectStep<U> {
+    fn project_step(&mut self) -> &mut U;
+}
+
+impl<T: UninitProject<U>, U> UninitProjectStep<U> for MaybeUninit<T> {
+    #[inline]
+    fn project_step(&mut self) -> &mut U {
+        T::uninit_project(self)
+    }
+}
+
+/// Drops a `T` unless it is forgotten.
+pub struct DropGuard<T>(*mut T);
+
+impl<T> DropGuard<T> {
+    #[inline]
+    pub const unsafe fn new(ptr: *mut T) -> Self {
+        Self(ptr)
+    }
+}
+
+impl<T> Drop for DropGuard<T> {
+    fn drop(&mut self) {
+        unsafe { core::ptr::drop_in_place(self.0) }
+    }
+}
+
+/// Deserializes into `this` by `f`, which sets the flag once its visitor has initialized `this`.
+///
+/// A deserializer may still fail after the visitor succeeds, such as on trailing input,
+/// and then `this` is dropped.
+#[cfg(feature = "serde")]
+pub fn deserialize_visited<T, E>(
+    this: &mut MaybeUninit<T>,
+    f: impl FnOnce(&mut MaybeUninit<T>, &mut bool) -> Result<(), E>,
+) -> Result<(), E> {
+    let mut visited = false;
+    let ret = f(this, &mut visited);
+    if ret.is_err() && visited {
+        unsafe { core::ptr::drop_in_place(this.as_mut_ptr()) }
+    }
+    ret
 }
 
 #[cfg(feature = "derive")]
-pub use placement_new_derive::__uninit_project_variant;
+pub use placement_new_derive::{__uninit_project_union, __uninit_project_variant};
@@ -1,30 +1,51 @@
 use crate::guard::guard_on_unwind;
-use crate::{SinglePlace, SlicePlace};
+use crate::{Place, PlaceTarget};
 
-use core::{mem, slice};
+use core::convert::Infallible;
 
 use rust_alloc::alloc::Layout;
 use rust_alloc::alloc::{alloc, alloc_zeroed, dealloc, handle_alloc_error};
 use rust_alloc::boxed::Box;
 
 #[inline]
-unsafe fn emplace(
+unsafe fn try_emplace<E>(
     layout: Layout,
     a: unsafe fn(Layout) -> *mut u8,
-    f: impl FnOnce(*mut ()),
-) -> *mut () {
+    f: impl FnOnce(*mut ()) -> Result<(), E>,
+) -> Result<*mut (), E> {
     if layout.size() == 0 {
         let ptr = layout.align() as *mut ();
-        f(ptr);
-        return ptr;
+        f(ptr)?;
+        return Ok(ptr);
     }
 
     let ptr = a(layout).cast::<()>();
     if ptr.is_null() {
         handle_alloc_error(layout)
     }
-    guard_on_unwind(|| f(ptr), || dealloc(ptr.cast(), layout));
-    ptr
+    match guard_on_unwind(|| f(ptr), || dealloc(ptr.cast(), layout)) {
+        Ok(()) => Ok(ptr),
+        Err(e) => {
+            dealloc(ptr.cast(), layout);
+            Err(e)
+        }
+    }
+}
+
+#[inline]
+unsafe fn emplace(
+    layout: Layout,
+    a: unsafe fn(Layout) -> *mut u8,
+    f: impl FnOnce(*mut ()),
+) -> *mut () {
+    let ret = try_emplace(layout, a, |ptr| {
+        f(ptr);
+        Ok::<(), Infallible>(())
+    });
+    match ret {
+        Ok(ptr) => ptr,
+        Err(e) => match e {},
+    }
 }
 
 /// Allocates memory and initialize it.
@@ -39,28 +60,139 @@ pub fn emplace_zeroed_with(layout: Layout, f: impl FnOnce(*mut ())) -> *mut () {
     unsafe { emplace(layout, alloc_zeroed, f) }
 }
 
-impl<T> SinglePlace<T> for Box<T> {
-    unsafe fn emplace_with(f: impl FnOnce(&mut mem::MaybeUninit<T>)) -> Self {
-        let ptr = emplace_with(Layout::new::<T>(), |ptr| f(&mut *ptr.cast()));
-        Self::from_raw(ptr.cast())
+/// Allocates memory and tries to initialize it.
+///
+/// If `f` returns an error, the memory is freed.
+///
+/// # Errors
+/// Returns the error of `f`.
+#[inline]
+pub fn try_emplace_with<E>(
+    layout: Layout,
+    f: impl FnOnce(*mut ()) -> Result<(), E>,
+) -> Result<*mut (), E> {
+    unsafe { try_emplace(layout, alloc, f) }
+}
+
+impl<T: ?Sized + PlaceTarget> Place<T> for Box<T> {
+    unsafe fn try_emplace_raw_with<E>(
+        meta: T::Metadata,
+        f: impl FnOnce(*mut T) -> Result<(), E>,
+    ) -> Result<Self, E> {
+        let ptr = try_emplace(T::layout(meta), alloc, |ptr| {
+            f(T::from_raw_parts(ptr, meta))
+        })?;
+        Ok(Self::from_raw(T::from_raw_parts(ptr, meta)))
     }
 
-    unsafe fn emplace_zeroed_with(f: impl FnOnce(&mut mem::MaybeUninit<T>)) -> Self {
-        let ptr = emplace_zeroed_with(Layout::new::<T>(), |ptr| f(&mut *ptr.cast()));
-        Self::from_raw(ptr.cast())
+    unsafe fn try_emplace_zeroed_raw_with<E>(
+        meta: T::Metadata,
+        f: impl FnOnce(*mut T) -> Result<(), E>,
+    ) -> Result<Self, E> {
+        let ptr = try_emplace(T::layout(meta), alloc_zeroed, |ptr| {
+            f(T::from_raw_parts(ptr, meta))
+        })?;
+        Ok(Self::from_raw(T::from_raw_parts(ptr, meta)))
     }
 }
 
-impl<T> SlicePlace<T> for Box<[T]> {
-    unsafe fn emplace_with(len: usize, f: impl FnOnce(&mut [mem::MaybeUninit<T>])) -> Self {
-        let layout = Layout::array::<T>(len).expect("invalid layout");
-        let ptr = emplace_with(layout, |ptr| f(slice::from_raw_parts_mut(ptr.cast(), len)));
-        Self::from_raw(slice::from_raw_parts_mut(ptr.cast(), len))
-    }
+macro_rules! impl_place_for_shared {
+    ($($module:ident: $rc:ident),+) => {
+        $(
+            mod $module {
+                use crate::Place;
 
-    unsafe fn emplace_zeroed_with(len: usize, f: impl FnOnce(&mut [mem::MaybeUninit<T>])) -> Self {
-        let layout = Layout::array::<T>(len).expect("invalid layout");
-        let ptr = emplace_zeroed_with(layout, |ptr| f(slice::from_raw_parts_mut(ptr.cast(), len)));
-        Self::from_raw(slice::from_raw_parts_mut(ptr.cast(), len))
-    }
+                use core::ptr;
+
+                use rust_alloc::$module::$rc;
+
+                // the pointer must not be shared yet
+                unsafe fn unique<T: ?Sized>(rc: &mut $rc<T>) -> &mut T {
+                    $rc::get_mut(rc).unwrap_unchecked()
+                }
+
+                unsafe fn try_emplace<T, E>(
+                    zeroed: bool,
+                    f: impl FnOnce(*mut T) -> Result<(), E>,
+                ) -> Result<$rc<T>, E> {
+                    let mut rc = $rc::<T>::new_uninit();
+                    let this = unique(&mut rc).as_mut_ptr();
+                    if zeroed {
+                        this.write_bytes(0, 1);
+                    }
+                    f(this)?;
+                    Ok(rc.assume_init())
+                }
+
+                unsafe fn try_emplace_slice<T, E>(
+                    len: usize,
+                    zeroed: bool,
+                    f: impl FnOnce(*mut [T]) -> Result<(), E>,
+                ) -> Result<$rc<[T]>, E> {
+                    let mut rc = $rc::<[T]>::new_uninit_slice(len);
+                    let this = unique(&mut rc).as_mut_ptr().cast::<T>();
+                    if zeroed {
+                        this.write_bytes(0, len);
+                    }
+                    f(ptr::slice_from_raw_parts_mut(this, len))?;
+                    Ok(rc.assume_init())
+                }
+
+                impl<T> Place<T> for $rc<T> {
+                    unsafe fn try_emplace_raw_with<E>(
+                        (): (),
+                        f: impl FnOnce(*mut T) -> Result<(), E>,
+                    ) -> Result<Self, E> {
+                        try_emplace(false, f)
+                    }
+
+                    unsafe fn try_emplace_zeroed_raw_with<E>(
+                        (): (),
+                        f: impl FnOnce(*mut T) -> Result<(), E>,
+                    ) -> Result<Self, E> {
+                        try_emplace(true, f)
+                    }
+                }
+
+                impl<T> Place<[T]> for $rc<[T]> {
+                    unsafe fn try_emplace_raw_with<E>(
+                        len: usize,
+                        f: impl FnOnce(*mut [T]) -> Result<(), E>,
+                    ) -> Result<Self, E> {
+                        try_emplace_slice(len, false, f)
+                    }
+
+                    unsafe fn try_emplace_zeroed_raw_with<E>(
+                        len: usize,
+                        f: impl FnOnce(*mut [T]) -> Result<(), E>,
+                    ) -> Result<Self, E> {
+                        try_emplace_slice(len, true, f)
+                    }
+                }
+
+                impl Place<str> for $rc<str> {
+                    unsafe fn try_emplace_raw_with<E>(
+                        len: usize,
+                        f: impl FnOnce(*mut str) -> Result<(), E>,
+                    ) -> Result<Self, E> {
+                        let rc = try_emplace_slice::<u8, E>(len, false, |ptr| f(ptr as *mut str))?;
+                        Ok($rc::from_raw($rc::into_raw(rc) as *const str))
+                    }
+
+                    unsafe fn try_emplace_zeroed_raw_with<E>(
+                        len: usize,
+                        f: impl FnOnce(*mut str) -> Result<(), E>,
+                    ) -> Result<Self, E> {
+                        let rc = try_emplace_slice::<u8, E>(len, true, |ptr| f(ptr as *mut str))?;
+                        Ok($rc::from_raw($rc::into_raw(rc) as *const str))
+                    }
+                }
+            }
+        )+
+    };
 }
+
+impl_place_for_shared!(rc: Rc);
+
+#[cfg(target_has_atomic = "ptr")]
+impl_place_for_shared!(sync: Arc);
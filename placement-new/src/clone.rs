@@ -0,0 +1,163 @@
+use crate::{init_slice_with, overwrite, UninitProject};
+
+use core::marker::PhantomData;
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::num::{self, Wrapping};
+use core::ptr::{self, NonNull};
+
+/// A type which can clone itself into uninitialized memory.
+///
+/// There is no blanket impl for `Copy` types,
+/// because it would overlap the impls of `Option<T>`, `[T; N]` and others.
+/// A `Copy` type implements it by `#[placement(copy)]` of the derive, or by [`copy_into`].
+///
+/// # Safety
+/// `clone_into_uninit` must initialize `dst` correctly.
+pub unsafe trait CloneInto: Sized {
+    /// Clones `self` into `dst`.
+    fn clone_into_uninit(&self, dst: &mut MaybeUninit<Self>);
+}
+
+/// Copies `src` into `dst` bit by bit, without a temporary on the stack.
+#[inline]
+#[allow(clippy::missing_const_for_fn)] // MSRV
+pub fn copy_into<T: Copy>(src: &T, dst: &mut MaybeUninit<T>) {
+    unsafe { ptr::copy_nonoverlapping(src, dst.as_mut_ptr(), 1) }
+}
+
+macro_rules! impl_clone_into_for_copy {
+    ($($ty:ty),+) => {
+        $(
+            unsafe impl CloneInto for $ty {
+                #[inline]
+                fn clone_into_uninit(&self, dst: &mut MaybeUninit<Self>) {
+                    overwrite(dst, *self)
+                }
+            }
+        )+
+    };
+}
+
+impl_clone_into_for_copy!(u8, u16, u32, u64, u128, usize);
+impl_clone_into_for_copy!(i8, i16, i32, i64, i128, isize);
+impl_clone_into_for_copy!(f32, f64, bool, char, ());
+
+impl_clone_into_for_copy!(
+    num::NonZeroU8,
+    num::NonZeroU16,
+    num::NonZeroU32,
+    num::NonZeroU64,
+    num::NonZeroU128,
+    num::NonZeroUsize
+);
+impl_clone_into_for_copy!(
+    num::NonZeroI8,
+    num::NonZeroI16,
+    num::NonZeroI32,
+    num::NonZeroI64,
+    num::NonZeroI128,
+    num::NonZeroIsize
+);
+
+unsafe impl<T: ?Sized> CloneInto for *const T {
+    #[inline]
+    fn clone_into_uninit(&self, dst: &mut MaybeUninit<Self>) {
+        overwrite(dst, *self);
+    }
+}
+
+unsafe impl<T: ?Sized> CloneInto for *mut T {
+    #[inline]
+    fn clone_into_uninit(&self, dst: &mut MaybeUninit<Self>) {
+        overwrite(dst, *self);
+    }
+}
+
+unsafe impl<T: ?Sized> CloneInto for NonNull<T> {
+    #[inline]
+    fn clone_into_uninit(&self, dst: &mut MaybeUninit<Self>) {
+        overwrite(dst, *self);
+    }
+}
+
+unsafe impl<T: ?Sized> CloneInto for &T {
+    #[inline]
+    fn clone_into_uninit(&self, dst: &mut MaybeUninit<Self>) {
+        overwrite(dst, *self);
+    }
+}
+
+unsafe impl<T: ?Sized> CloneInto for PhantomData<T> {
+    #[inline]
+    fn clone_into_uninit(&self, dst: &mut MaybeUninit<Self>) {
+        overwrite(dst, Self);
+    }
+}
+
+unsafe impl<T: Clone> CloneInto for Option<T> {
+    #[inline]
+    fn clone_into_uninit(&self, dst: &mut MaybeUninit<Self>) {
+        overwrite(dst, self.clone());
+    }
+}
+
+unsafe impl<T: CloneInto, const N: usize> CloneInto for [T; N] {
+    #[inline]
+    fn clone_into_uninit(&self, dst: &mut MaybeUninit<Self>) {
+        unsafe {
+            init_slice_with(UninitProject::uninit_project(dst), |idx, this| {
+                self.get_unchecked(idx).clone_into_uninit(this);
+            });
+        }
+    }
+}
+
+macro_rules! impl_clone_into_for_wrapper {
+    ($($ty:ident),+) => {
+        $(
+            unsafe impl<T: CloneInto> CloneInto for $ty<T> {
+                #[inline]
+                fn clone_into_uninit(&self, dst: &mut MaybeUninit<Self>) {
+                    // `$ty<T>` has the same layout as `T`
+                    let src: &T = unsafe { &*ptr::addr_of!(*self).cast() };
+                    src.clone_into_uninit(unsafe { &mut *dst.as_mut_ptr().cast() });
+                }
+            }
+        )+
+    };
+}
+
+impl_clone_into_for_wrapper!(ManuallyDrop, Wrapping);
+
+#[cfg(feature = "alloc")]
+mod alloc_impls {
+    use super::CloneInto;
+    use crate::{overwrite, SinglePlace};
+
+    use core::mem::MaybeUninit;
+
+    use rust_alloc::boxed::Box;
+    use rust_alloc::string::String;
+    use rust_alloc::vec::Vec;
+
+    unsafe impl<T: CloneInto> CloneInto for Box<T> {
+        #[inline]
+        fn clone_into_uninit(&self, dst: &mut MaybeUninit<Self>) {
+            overwrite(dst, Self::emplace_clone(self));
+        }
+    }
+
+    unsafe impl CloneInto for String {
+        #[inline]
+        fn clone_into_uninit(&self, dst: &mut MaybeUninit<Self>) {
+            overwrite(dst, self.clone());
+        }
+    }
+
+    unsafe impl<T: Clone> CloneInto for Vec<T> {
+        #[inline]
+        fn clone_into_uninit(&self, dst: &mut MaybeUninit<Self>) {
+            overwrite(dst, self.clone());
+        }
+    }
+}
@@ -0,0 +1,331 @@
+use crate::__private::deserialize_visited;
+use crate::{overwrite, UninitProject};
+
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem::{self, MaybeUninit};
+use core::num::{self, Wrapping};
+use core::ptr;
+
+use serde::de::{self, Deserialize, DeserializeSeed, Deserializer, SeqAccess, Visitor};
+
+/// A type which can deserialize itself into uninitialized memory.
+///
+/// # Safety
+/// + If `deserialize_in_place` returns `Ok`, `this` must be initialized correctly.
+/// + If `deserialize_in_place` returns `Err` or panics,
+///   every value written into `this` must have been dropped.
+pub unsafe trait DeserializeInPlace<'de>: Sized {
+    /// Deserializes a value into `this`.
+    ///
+    /// # Errors
+    /// Returns the error of `deserializer`.
+    fn deserialize_in_place<D>(
+        deserializer: D,
+        this: &mut MaybeUninit<Self>,
+    ) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>;
+}
+
+/// A [`DeserializeSeed`] which deserializes a `T` into the given place.
+///
+/// It drives [`DeserializeInPlace`] through `SeqAccess::next_element_seed`
+/// and `MapAccess::next_value_seed`.
+#[derive(Debug)]
+pub struct UninitSeed<'a, T>(pub &'a mut MaybeUninit<T>);
+
+impl<'de, T: DeserializeInPlace<'de>> DeserializeSeed<'de> for UninitSeed<'_, T> {
+    type Value = ();
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        T::deserialize_in_place(deserializer, self.0)
+    }
+}
+
+macro_rules! impl_deserialize_in_place_by_value {
+    ($($ty:ty),+) => {
+        $(
+            unsafe impl<'de> DeserializeInPlace<'de> for $ty {
+                #[inline]
+                fn deserialize_in_place<D>(deserializer: D, this: &mut MaybeUninit<Self>) -> Result<(), D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    overwrite(this, <$ty>::deserialize(deserializer)?);
+                    Ok(())
+                }
+            }
+        )+
+    };
+}
+
+impl_deserialize_in_place_by_value!(u8, u16, u32, u64, u128, usize);
+impl_deserialize_in_place_by_value!(i8, i16, i32, i64, i128, isize);
+impl_deserialize_in_place_by_value!(f32, f64, bool, char, ());
+
+impl_deserialize_in_place_by_value!(
+    num::NonZeroU8,
+    num::NonZeroU16,
+    num::NonZeroU32,
+    num::NonZeroU64,
+    num::NonZeroU128,
+    num::NonZeroUsize
+);
+impl_deserialize_in_place_by_value!(
+    num::NonZeroI8,
+    num::NonZeroI16,
+    num::NonZeroI32,
+    num::NonZeroI64,
+    num::NonZeroI128,
+    num::NonZeroIsize
+);
+
+unsafe impl<'de, T: Deserialize<'de>> DeserializeInPlace<'de> for Option<T> {
+    #[inline]
+    fn deserialize_in_place<D>(
+        deserializer: D,
+        this: &mut MaybeUninit<Self>,
+    ) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        overwrite(this, Self::deserialize(deserializer)?);
+        Ok(())
+    }
+}
+
+unsafe impl<'de, T: ?Sized> DeserializeInPlace<'de> for PhantomData<T> {
+    #[inline]
+    fn deserialize_in_place<D>(
+        deserializer: D,
+        this: &mut MaybeUninit<Self>,
+    ) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        overwrite(this, Self::deserialize(deserializer)?);
+        Ok(())
+    }
+}
+
+struct ArrayLen(usize);
+
+impl de::Expected for ArrayLen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "an array of length {}", self.0)
+    }
+}
+
+struct ArrayVisitor<'a, T, const N: usize>(&'a mut [MaybeUninit<T>], &'a mut bool);
+
+impl<'de, T: DeserializeInPlace<'de>, const N: usize> Visitor<'de> for ArrayVisitor<'_, T, N> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        de::Expected::fmt(&ArrayLen(N), f)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<(), A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        struct Partial<T>(*mut T, usize);
+
+        impl<T> Drop for Partial<T> {
+            fn drop(&mut self) {
+                unsafe { ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.0, self.1)) }
+            }
+        }
+
+        let mut partial = Partial(self.0.as_mut_ptr().cast::<T>(), 0);
+        for this in self.0.iter_mut() {
+            if seq.next_element_seed(UninitSeed(this))?.is_none() {
+                return Err(de::Error::invalid_length(partial.1, &ArrayLen(N)));
+            }
+            partial.1 += 1;
+        }
+        mem::forget(partial);
+        *self.1 = true;
+        Ok(())
+    }
+}
+
+unsafe impl<'de, T: DeserializeInPlace<'de>, const N: usize> DeserializeInPlace<'de> for [T; N] {
+    #[inline]
+    fn deserialize_in_place<D>(
+        deserializer: D,
+        this: &mut MaybeUninit<Self>,
+    ) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_visited(this, |this, visited| {
+            let slice = UninitProject::uninit_project(this);
+            deserializer.deserialize_tuple(N, ArrayVisitor::<T, N>(slice, visited))
+        })
+    }
+}
+
+macro_rules! impl_deserialize_in_place_for_wrapper {
+    ($($ty:ident),+) => {
+        $(
+            unsafe impl<'de, T: DeserializeInPlace<'de>> DeserializeInPlace<'de> for $ty<T> {
+                #[inline]
+                fn deserialize_in_place<D>(deserializer: D, this: &mut MaybeUninit<Self>) -> Result<(), D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    // `$ty<T>` has the same layout as `T`
+                    T::deserialize_in_place(deserializer, unsafe { &mut *this.as_mut_ptr().cast() })
+                }
+            }
+        )+
+    };
+}
+
+impl_deserialize_in_place_for_wrapper!(Wrapping);
+
+#[cfg(feature = "alloc")]
+mod alloc_impls {
+    use super::{DeserializeInPlace, UninitSeed};
+    use crate::{overwrite, SinglePlace};
+
+    use core::fmt;
+    use core::marker::PhantomData;
+    use core::mem::{self, MaybeUninit};
+
+    use rust_alloc::boxed::Box;
+    use rust_alloc::string::String;
+    use rust_alloc::vec::Vec;
+
+    use serde::de::{Deserialize, Deserializer, SeqAccess, Visitor};
+
+    /// A place containing multiple continuous values, whose length is decided by the deserializer.
+    pub trait DeserializeSeqPlace<'de, T>: Sized {
+        /// Deserializes a sequence and writes every element directly into the place.
+        ///
+        /// If an element fails, every element deserialized before will be dropped.
+        ///
+        /// The length is unknown until the sequence ends, so the elements are written into
+        /// a growing buffer, and they are moved whenever it grows.
+        /// `Box<[T]>` also shrinks the buffer to fit, which may reallocate and copy the elements.
+        ///
+        /// # Errors
+        /// Returns the error of `deserializer`.
+        fn emplace_deserialize_seq<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>;
+    }
+
+    struct VecVisitor<T>(PhantomData<T>);
+
+    impl<'de, T: DeserializeInPlace<'de>> Visitor<'de> for VecVisitor<T> {
+        type Value = Vec<T>;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a sequence")
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Vec<T>, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            // do not trust the size hint too much
+            let max_hint = 4096 / mem::size_of::<T>().max(1);
+            let mut vec = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(max_hint));
+            loop {
+                vec.reserve(1);
+                let slot = &mut vec.spare_capacity_mut()[0];
+                if seq.next_element_seed(UninitSeed(slot))?.is_none() {
+                    return Ok(vec);
+                }
+                unsafe { vec.set_len(vec.len() + 1) };
+            }
+        }
+    }
+
+    impl<'de, T: DeserializeInPlace<'de>> DeserializeSeqPlace<'de, T> for Vec<T> {
+        #[inline]
+        fn emplace_deserialize_seq<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_seq(VecVisitor(PhantomData))
+        }
+    }
+
+    impl<'de, T: DeserializeInPlace<'de>> DeserializeSeqPlace<'de, T> for Box<[T]> {
+        #[inline]
+        fn emplace_deserialize_seq<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            // it copies the elements unless the capacity is already exact
+            Vec::emplace_deserialize_seq(deserializer).map(Vec::into_boxed_slice)
+        }
+    }
+
+    unsafe impl<'de, T: DeserializeInPlace<'de>> DeserializeInPlace<'de> for Box<T> {
+        #[inline]
+        fn deserialize_in_place<D>(
+            deserializer: D,
+            this: &mut MaybeUninit<Self>,
+        ) -> Result<(), D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            overwrite(this, Self::emplace_deserialize(deserializer)?);
+            Ok(())
+        }
+    }
+
+    unsafe impl<'de, T: DeserializeInPlace<'de>> DeserializeInPlace<'de> for Box<[T]> {
+        #[inline]
+        fn deserialize_in_place<D>(
+            deserializer: D,
+            this: &mut MaybeUninit<Self>,
+        ) -> Result<(), D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            overwrite(this, Self::emplace_deserialize_seq(deserializer)?);
+            Ok(())
+        }
+    }
+
+    unsafe impl<'de, T: DeserializeInPlace<'de>> DeserializeInPlace<'de> for Vec<T> {
+        #[inline]
+        fn deserialize_in_place<D>(
+            deserializer: D,
+            this: &mut MaybeUninit<Self>,
+        ) -> Result<(), D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            overwrite(this, Self::emplace_deserialize_seq(deserializer)?);
+            Ok(())
+        }
+    }
+
+    unsafe impl<'de> DeserializeInPlace<'de> for String {
+        #[inline]
+        fn deserialize_in_place<D>(
+            deserializer: D,
+            this: &mut MaybeUninit<Self>,
+        ) -> Result<(), D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            overwrite(this, Self::deserialize(deserializer)?);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub use self::alloc_impls::DeserializeSeqPlace;
@@ -0,0 +1,153 @@
+use crate::{init_slice_with, overwrite, UninitProject};
+
+use core::cell::{Cell, UnsafeCell};
+use core::marker::PhantomData;
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::num::Wrapping;
+
+/// A type which can initialize its default value in place.
+///
+/// # Safety
+/// `default_in_place` must initialize `Self` correctly.
+pub unsafe trait DefaultInPlace: Sized {
+    /// Initializes the default value of `Self`.
+    fn default_in_place(this: &mut MaybeUninit<Self>);
+}
+
+macro_rules! impl_default_in_place {
+    ($($ty:ty),+) => {
+        $(
+            unsafe impl DefaultInPlace for $ty {
+                #[inline]
+                fn default_in_place(this: &mut MaybeUninit<Self>) {
+                    overwrite(this, Default::default())
+                }
+            }
+        )+
+    };
+}
+
+impl_default_in_place!(u8, u16, u32, u64, u128, usize);
+impl_default_in_place!(i8, i16, i32, i64, i128, isize);
+impl_default_in_place!(f32, f64, bool, char, ());
+
+unsafe impl<T> DefaultInPlace for Option<T> {
+    #[inline]
+    fn default_in_place(this: &mut MaybeUninit<Self>) {
+        overwrite(this, None);
+    }
+}
+
+unsafe impl<T: ?Sized> DefaultInPlace for PhantomData<T> {
+    #[inline]
+    fn default_in_place(this: &mut MaybeUninit<Self>) {
+        overwrite(this, Self);
+    }
+}
+
+unsafe impl<T: DefaultInPlace, const N: usize> DefaultInPlace for [T; N] {
+    #[inline]
+    fn default_in_place(this: &mut MaybeUninit<Self>) {
+        unsafe {
+            init_slice_with(UninitProject::uninit_project(this), |_, this| {
+                T::default_in_place(this);
+            });
+        }
+    }
+}
+
+macro_rules! impl_default_in_place_for_wrapper {
+    ($($ty:ident),+) => {
+        $(
+            unsafe impl<T: DefaultInPlace> DefaultInPlace for $ty<T> {
+                #[inline]
+                fn default_in_place(this: &mut MaybeUninit<Self>) {
+                    // `$ty<T>` has the same layout as `T`
+                    T::default_in_place(unsafe { &mut *this.as_mut_ptr().cast() });
+                }
+            }
+        )+
+    };
+}
+
+impl_default_in_place_for_wrapper!(ManuallyDrop, Wrapping, Cell, UnsafeCell);
+
+#[cfg(feature = "alloc")]
+mod alloc_impls {
+    use super::DefaultInPlace;
+    use crate::{overwrite, SinglePlace};
+
+    use core::mem::MaybeUninit;
+
+    use rust_alloc::boxed::Box;
+    use rust_alloc::collections::{BTreeMap, BTreeSet, VecDeque};
+    use rust_alloc::string::String;
+    use rust_alloc::vec::Vec;
+
+    unsafe impl<T: DefaultInPlace> DefaultInPlace for Box<T> {
+        #[inline]
+        fn default_in_place(this: &mut MaybeUninit<Self>) {
+            overwrite(this, Self::emplace_default());
+        }
+    }
+
+    unsafe impl DefaultInPlace for String {
+        #[inline]
+        fn default_in_place(this: &mut MaybeUninit<Self>) {
+            overwrite(this, Self::new());
+        }
+    }
+
+    unsafe impl<T> DefaultInPlace for Vec<T> {
+        #[inline]
+        fn default_in_place(this: &mut MaybeUninit<Self>) {
+            overwrite(this, Self::new());
+        }
+    }
+
+    unsafe impl<T> DefaultInPlace for VecDeque<T> {
+        #[inline]
+        fn default_in_place(this: &mut MaybeUninit<Self>) {
+            overwrite(this, Self::new());
+        }
+    }
+
+    unsafe impl<K, V> DefaultInPlace for BTreeMap<K, V> {
+        #[inline]
+        fn default_in_place(this: &mut MaybeUninit<Self>) {
+            overwrite(this, Self::new());
+        }
+    }
+
+    unsafe impl<T> DefaultInPlace for BTreeSet<T> {
+        #[inline]
+        fn default_in_place(this: &mut MaybeUninit<Self>) {
+            overwrite(this, Self::new());
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+mod std_impls {
+    use super::DefaultInPlace;
+    use crate::overwrite;
+
+    use core::mem::MaybeUninit;
+
+    use std::collections::{HashMap, HashSet};
+    use std::hash::BuildHasher;
+
+    unsafe impl<K, V, S: BuildHasher + Default> DefaultInPlace for HashMap<K, V, S> {
+        #[inline]
+        fn default_in_place(this: &mut MaybeUninit<Self>) {
+            overwrite(this, Self::default());
+        }
+    }
+
+    unsafe impl<T, S: BuildHasher + Default> DefaultInPlace for HashSet<T, S> {
+        #[inline]
+        fn default_in_place(this: &mut MaybeUninit<Self>) {
+            overwrite(this, Self::default());
+        }
+    }
+}
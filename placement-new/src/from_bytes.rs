@@ -0,0 +1,258 @@
+use crate::guard::Guard;
+use crate::{overwrite, UninitProject};
+
+use core::cell::Cell;
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem::{self, ManuallyDrop, MaybeUninit};
+use core::num::{self, Wrapping};
+use core::ptr;
+
+/// The reason why a value cannot be read from bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FromBytesErrorKind {
+    /// The bytes end before the value.
+    UnexpectedEnd,
+    /// A `bool` is neither 0 nor 1.
+    InvalidBool,
+    /// A `char` is not a unicode scalar value.
+    InvalidChar,
+    /// A non-zero integer is zero.
+    Zero,
+    /// An enum discriminant matches no variant.
+    InvalidDiscriminant,
+}
+
+/// An error which occurs when a value is read from bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FromBytesError {
+    offset: usize,
+    kind: FromBytesErrorKind,
+}
+
+impl FromBytesError {
+    /// Creates an error of the value at `offset`.
+    #[must_use]
+    pub const fn new(offset: usize, kind: FromBytesErrorKind) -> Self {
+        Self { offset, kind }
+    }
+
+    /// Returns the offset of the offending value, relative to the start of the bytes.
+    #[must_use]
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the reason of the error.
+    #[must_use]
+    pub const fn kind(&self) -> FromBytesErrorKind {
+        self.kind
+    }
+
+    /// Moves the offset forward by `base`.
+    ///
+    /// It is used to report the offset of a field relative to its container.
+    #[must_use]
+    pub const fn add_offset(self, base: usize) -> Self {
+        Self {
+            offset: self.offset + base,
+            kind: self.kind,
+        }
+    }
+}
+
+impl fmt::Display for FromBytesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self.kind {
+            FromBytesErrorKind::UnexpectedEnd => "unexpected end of bytes",
+            FromBytesErrorKind::InvalidBool => "invalid bool",
+            FromBytesErrorKind::InvalidChar => "invalid char",
+            FromBytesErrorKind::Zero => "zero in a non-zero integer",
+            FromBytesErrorKind::InvalidDiscriminant => "invalid enum discriminant",
+        };
+        write!(f, "{} at offset {}", reason, self.offset)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FromBytesError {}
+
+/// A type which can be read from its in-memory byte representation and validated.
+///
+/// The bytes are in native byte order, laid out as `Self` is laid out in memory.
+/// Padding bytes are ignored, and bytes after `Self` are ignored.
+///
+/// # Safety
+/// + If `from_bytes_in_place` returns `Ok`, `this` must be initialized correctly.
+/// + If `from_bytes_in_place` returns `Ok`, `this` must hold the same bytes as `bytes`,
+///   except for padding, so that validated bytes can be used as a `Self` in place.
+/// + If `from_bytes_in_place` returns `Err` or panics,
+///   every value written into `this` must have been dropped.
+pub unsafe trait FromBytesInPlace: Sized {
+    /// Reads `Self` from the start of `bytes` into `this`.
+    ///
+    /// # Errors
+    /// Returns an error whose offset is relative to the start of `bytes`,
+    /// if `bytes` is too short or contains an invalid value.
+    fn from_bytes_in_place(
+        bytes: &[u8],
+        this: &mut MaybeUninit<Self>,
+    ) -> Result<(), FromBytesError>;
+}
+
+/// Reads a `T` whose every bit pattern is valid.
+#[inline]
+#[allow(clippy::missing_const_for_fn)] // MSRV
+fn read_bits<T: Copy>(bytes: &[u8]) -> Result<T, FromBytesError> {
+    if bytes.len() < mem::size_of::<T>() {
+        return Err(FromBytesError::new(0, FromBytesErrorKind::UnexpectedEnd));
+    }
+    // every bit pattern of `T` is valid
+    Ok(unsafe { ptr::read_unaligned(bytes.as_ptr().cast::<T>()) })
+}
+
+macro_rules! impl_from_bytes_in_place_for_bits {
+    ($($ty:ty),+) => {
+        $(
+            unsafe impl FromBytesInPlace for $ty {
+                #[inline]
+                fn from_bytes_in_place(bytes: &[u8], this: &mut MaybeUninit<Self>) -> Result<(), FromBytesError> {
+                    overwrite(this, read_bits::<$ty>(bytes)?);
+                    Ok(())
+                }
+            }
+        )+
+    };
+}
+
+impl_from_bytes_in_place_for_bits!(u8, u16, u32, u64, u128, usize);
+impl_from_bytes_in_place_for_bits!(i8, i16, i32, i64, i128, isize);
+impl_from_bytes_in_place_for_bits!(f32, f64, ());
+
+impl_from_bytes_in_place_for_bits!(
+    Option<num::NonZeroU8>,
+    Option<num::NonZeroU16>,
+    Option<num::NonZeroU32>,
+    Option<num::NonZeroU64>,
+    Option<num::NonZeroU128>,
+    Option<num::NonZeroUsize>
+);
+impl_from_bytes_in_place_for_bits!(
+    Option<num::NonZeroI8>,
+    Option<num::NonZeroI16>,
+    Option<num::NonZeroI32>,
+    Option<num::NonZeroI64>,
+    Option<num::NonZeroI128>,
+    Option<num::NonZeroIsize>
+);
+
+unsafe impl FromBytesInPlace for bool {
+    #[inline]
+    fn from_bytes_in_place(
+        bytes: &[u8],
+        this: &mut MaybeUninit<Self>,
+    ) -> Result<(), FromBytesError> {
+        match read_bits::<u8>(bytes)? {
+            0 => overwrite(this, false),
+            1 => overwrite(this, true),
+            _ => return Err(FromBytesError::new(0, FromBytesErrorKind::InvalidBool)),
+        }
+        Ok(())
+    }
+}
+
+unsafe impl FromBytesInPlace for char {
+    #[inline]
+    fn from_bytes_in_place(
+        bytes: &[u8],
+        this: &mut MaybeUninit<Self>,
+    ) -> Result<(), FromBytesError> {
+        let c = Self::from_u32(read_bits::<u32>(bytes)?)
+            .ok_or_else(|| FromBytesError::new(0, FromBytesErrorKind::InvalidChar))?;
+        overwrite(this, c);
+        Ok(())
+    }
+}
+
+macro_rules! impl_from_bytes_in_place_for_nonzero {
+    ($($ty:ident: $int:ty),+) => {
+        $(
+            unsafe impl FromBytesInPlace for num::$ty {
+                #[inline]
+                fn from_bytes_in_place(bytes: &[u8], this: &mut MaybeUninit<Self>) -> Result<(), FromBytesError> {
+                    let n = Self::new(read_bits::<$int>(bytes)?)
+                        .ok_or_else(|| FromBytesError::new(0, FromBytesErrorKind::Zero))?;
+                    overwrite(this, n);
+                    Ok(())
+                }
+            }
+        )+
+    };
+}
+
+impl_from_bytes_in_place_for_nonzero!(
+    NonZeroU8: u8,
+    NonZeroU16: u16,
+    NonZeroU32: u32,
+    NonZeroU64: u64,
+    NonZeroU128: u128,
+    NonZeroUsize: usize
+);
+impl_from_bytes_in_place_for_nonzero!(
+    NonZeroI8: i8,
+    NonZeroI16: i16,
+    NonZeroI32: i32,
+    NonZeroI64: i64,
+    NonZeroI128: i128,
+    NonZeroIsize: isize
+);
+
+unsafe impl<T: ?Sized> FromBytesInPlace for PhantomData<T> {
+    #[inline]
+    fn from_bytes_in_place(_: &[u8], this: &mut MaybeUninit<Self>) -> Result<(), FromBytesError> {
+        overwrite(this, Self);
+        Ok(())
+    }
+}
+
+unsafe impl<T: FromBytesInPlace, const N: usize> FromBytesInPlace for [T; N] {
+    #[inline]
+    fn from_bytes_in_place(
+        bytes: &[u8],
+        this: &mut MaybeUninit<Self>,
+    ) -> Result<(), FromBytesError> {
+        let slice: &mut [MaybeUninit<T>] = UninitProject::uninit_project(this);
+        let base = slice.as_mut_ptr().cast::<T>();
+        let count = Cell::new(0);
+
+        let guard = Guard::new(|| unsafe {
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(base, count.get()));
+        });
+        for idx in 0..N {
+            let offset = idx * mem::size_of::<T>();
+            let this = unsafe { &mut *base.add(idx).cast::<MaybeUninit<T>>() };
+            T::from_bytes_in_place(bytes.get(offset..).unwrap_or(&[]), this)
+                .map_err(|e| e.add_offset(offset))?;
+            count.set(idx + 1);
+        }
+        guard.cancel();
+        Ok(())
+    }
+}
+
+macro_rules! impl_from_bytes_in_place_for_wrapper {
+    ($($ty:ident),+) => {
+        $(
+            unsafe impl<T: FromBytesInPlace> FromBytesInPlace for $ty<T> {
+                #[inline]
+                fn from_bytes_in_place(bytes: &[u8], this: &mut MaybeUninit<Self>) -> Result<(), FromBytesError> {
+                    // `$ty<T>` has the same layout as `T`
+                    T::from_bytes_in_place(bytes, unsafe { &mut *this.as_mut_ptr().cast() })
+                }
+            }
+        )+
+    };
+}
+
+impl_from_bytes_in_place_for_wrapper!(ManuallyDrop, Wrapping);
@@ -29,3 +29,9 @@ pub fn guard_on_unwind<R>(f: impl FnOnce() -> R, g: impl FnOnce()) -> R {
     guard.cancel();
     ret
 }
+
+#[inline]
+pub fn abort_on_unwind<R>(f: impl FnOnce() -> R) -> R {
+    // panicking while unwinding aborts the process
+    guard_on_unwind(f, || panic!("aborting on unwind"))
+}
@@ -0,0 +1,181 @@
+use crate::guard::Guard;
+use crate::{memset_zeroed, DefaultInPlace, Zeroable};
+
+use core::cell::Cell;
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem::{self, MaybeUninit};
+use core::ops::{Deref, DerefMut};
+use core::ptr;
+
+/// An uninitialized slot on the stack, which is given by [`with_uninit`].
+///
+/// The value is dropped at the end of [`with_uninit`] once it is initialized.
+pub struct StackSlot<'a, T> {
+    slot: &'a mut MaybeUninit<T>,
+    initialized: &'a Cell<bool>,
+}
+
+impl<'a, T> StackSlot<'a, T> {
+    /// Initializes the value by `f`, and returns it.
+    ///
+    /// If `f` panics, the value is not dropped.
+    ///
+    /// # Safety
+    /// `f` must initialize `T` correctly.
+    pub unsafe fn init_with(self, f: impl FnOnce(&mut MaybeUninit<T>)) -> &'a mut T {
+        f(self.slot);
+        self.initialized.set(true);
+        self.slot.assume_init_mut()
+    }
+
+    /// Moves `value` into the slot.
+    pub fn init(self, value: T) -> &'a mut T {
+        unsafe { self.init_with(|this| crate::overwrite(this, value)) }
+    }
+
+    /// Initializes the value by [`DefaultInPlace`].
+    pub fn init_default(self) -> &'a mut T
+    where
+        T: DefaultInPlace,
+    {
+        unsafe { self.init_with(T::default_in_place) }
+    }
+
+    /// Initializes the value with zero.
+    pub fn init_zeroed(self) -> &'a mut T
+    where
+        T: Zeroable,
+    {
+        unsafe { self.init_with(memset_zeroed) }
+    }
+}
+
+/// Passes an uninitialized slot of `T` on the stack to `f`.
+///
+/// If `f` initializes the slot, the value is dropped when `f` returns, even if `f` panics.
+pub fn with_uninit<T, R>(f: impl FnOnce(StackSlot<'_, T>) -> R) -> R {
+    let mut slot = MaybeUninit::<T>::uninit();
+    let initialized = &Cell::new(false);
+    let ptr = ptr::addr_of_mut!(slot);
+    let _guard = Guard::new(move || {
+        if initialized.get() {
+            unsafe { ptr::drop_in_place((*ptr).as_mut_ptr()) }
+        }
+    });
+    f(StackSlot {
+        slot: unsafe { &mut *ptr },
+        initialized,
+    })
+}
+
+/// Creates a `T` on the stack by [`DefaultInPlace`], passes it to `f`, and drops it.
+pub fn with_default<T: DefaultInPlace, R>(f: impl FnOnce(&mut T) -> R) -> R {
+    with_uninit(|slot| f(slot.init_default()))
+}
+
+/// Creates a zeroed `T` on the stack, passes it to `f`, and drops it.
+pub fn with_zeroed<T: Zeroable, R>(f: impl FnOnce(&mut T) -> R) -> R {
+    with_uninit(|slot| f(slot.init_zeroed()))
+}
+
+/// The inline storage of an [`InlineBox`], aligned to 16 bytes.
+#[repr(C, align(16))]
+struct InlineStorage<const CAP: usize>([MaybeUninit<u8>; CAP]);
+
+/// A value of an unsized type, usually a trait object, stored inline without heap allocation.
+///
+/// The concrete value must fit in `CAP` bytes, and its alignment must be at most 16.
+/// Both are checked at compile time.
+///
+/// Use [`inline_box!`](crate::inline_box) to create it safely.
+pub struct InlineBox<T: ?Sized, const CAP: usize> {
+    storage: InlineStorage<CAP>,
+    // an unsizing cast from the concrete type, whose argument type is erased
+    coerce: fn(*mut ()) -> *mut T,
+    _marker: PhantomData<T>,
+}
+
+impl<T: ?Sized, const CAP: usize> InlineBox<T, CAP> {
+    /// Initializes a `U` in the inline storage by `f`.
+    ///
+    /// # Safety
+    /// + `f` must initialize `U` correctly.
+    /// + `coerce` must be an unsizing cast, such as `|ptr| ptr as *mut dyn Trait`.
+    pub unsafe fn emplace_with<U>(
+        f: impl FnOnce(&mut MaybeUninit<U>),
+        coerce: fn(*mut U) -> *mut T,
+    ) -> Self {
+        const {
+            assert!(mem::size_of::<U>() <= CAP, "the value is too large");
+            assert!(mem::align_of::<U>() <= 16, "the value is over-aligned");
+        };
+        let mut storage = InlineStorage([MaybeUninit::uninit(); CAP]);
+        f(&mut *storage.0.as_mut_ptr().cast::<MaybeUninit<U>>());
+        Self {
+            storage,
+            // pointers to sized types are ABI-compatible
+            coerce: mem::transmute::<fn(*mut U) -> *mut T, fn(*mut ()) -> *mut T>(coerce),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Moves `value` into the inline storage.
+    ///
+    /// # Safety
+    /// `coerce` must be an unsizing cast, such as `|ptr| ptr as *mut dyn Trait`.
+    pub unsafe fn new<U>(value: U, coerce: fn(*mut U) -> *mut T) -> Self {
+        Self::emplace_with(|this| crate::overwrite(this, value), coerce)
+    }
+
+    #[inline]
+    fn as_ptr(&self) -> *mut T {
+        (self.coerce)(ptr::addr_of!(self.storage).cast_mut().cast())
+    }
+
+    #[inline]
+    fn as_mut_ptr(&mut self) -> *mut T {
+        (self.coerce)(ptr::addr_of_mut!(self.storage).cast())
+    }
+}
+
+/// Creates an [`InlineBox`] of an unsized type from a value.
+///
+/// `inline_box!(dyn Trait, value)` fails to compile if `value` cannot be cast to `dyn Trait`,
+/// or if it does not fit in the inline storage.
+#[macro_export]
+macro_rules! inline_box {
+    ($ty:ty, $value:expr) => {{
+        let value = $value;
+        // only an unsizing coercion is accepted by the return type
+        unsafe { $crate::InlineBox::new(value, |ptr| -> *mut $ty { ptr }) }
+    }};
+}
+
+impl<T: ?Sized, const CAP: usize> Deref for InlineBox<T, CAP> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        unsafe { &*self.as_ptr() }
+    }
+}
+
+impl<T: ?Sized, const CAP: usize> DerefMut for InlineBox<T, CAP> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.as_mut_ptr() }
+    }
+}
+
+impl<T: ?Sized, const CAP: usize> Drop for InlineBox<T, CAP> {
+    fn drop(&mut self) {
+        unsafe { ptr::drop_in_place(self.as_mut_ptr()) }
+    }
+}
+
+impl<T: ?Sized + fmt::Debug, const CAP: usize> fmt::Debug for InlineBox<T, CAP> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
@@ -0,0 +1,226 @@
+use crate::{init_slice_with, overwrite, DefaultInPlace, UninitProject, Zeroable};
+
+use core::fmt;
+use core::mem::MaybeUninit;
+use core::ops::{Deref, DerefMut};
+use core::{ptr, slice};
+
+/// A vector with a fixed capacity, whose elements are stored inline.
+///
+/// Every element is initialized directly in its slot,
+/// so a vector inside an emplaced struct never passes through the stack.
+pub struct InlineVec<T, const N: usize> {
+    len: usize,
+    buf: MaybeUninit<[T; N]>,
+}
+
+impl<T, const N: usize> InlineVec<T, N> {
+    /// Creates an empty vector.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            len: 0,
+            buf: MaybeUninit::uninit(),
+        }
+    }
+
+    /// Returns the number of elements.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the vector has no elements.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns whether the vector has `N` elements.
+    #[must_use]
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Returns the capacity, which is `N`.
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    #[inline]
+    fn slots(&mut self) -> &mut [MaybeUninit<T>; N] {
+        UninitProject::uninit_project(&mut self.buf)
+    }
+
+    /// Returns the uninitialized slot after the last element.
+    ///
+    /// # Panics
+    /// Panics if the vector is full.
+    #[inline]
+    fn next_slot(&mut self) -> &mut MaybeUninit<T> {
+        assert!(!self.is_full(), "the vector is full");
+        let len = self.len;
+        &mut self.slots()[len]
+    }
+
+    /// Initializes a new element at the end by `f`, and returns it.
+    ///
+    /// # Safety
+    /// `f` must initialize `T` correctly.
+    ///
+    /// # Panics
+    /// Panics if the vector is full.
+    pub unsafe fn emplace_push_with(&mut self, f: impl FnOnce(&mut MaybeUninit<T>)) -> &mut T {
+        let slot: *mut MaybeUninit<T> = self.next_slot();
+        f(&mut *slot);
+        self.len += 1;
+        (*slot).assume_init_mut()
+    }
+
+    /// Tries to initialize a new element at the end by `f`.
+    ///
+    /// If `f` returns an error, the length is unchanged.
+    ///
+    /// # Errors
+    /// Returns the error of `f`.
+    ///
+    /// # Safety
+    /// `f` must initialize `T` correctly if it returns `Ok`.
+    ///
+    /// # Panics
+    /// Panics if the vector is full.
+    pub unsafe fn try_emplace_push_with<E>(
+        &mut self,
+        f: impl FnOnce(&mut MaybeUninit<T>) -> Result<(), E>,
+    ) -> Result<&mut T, E> {
+        let slot: *mut MaybeUninit<T> = self.next_slot();
+        f(&mut *slot)?;
+        self.len += 1;
+        Ok((*slot).assume_init_mut())
+    }
+
+    /// Appends `value` to the end.
+    ///
+    /// # Panics
+    /// Panics if the vector is full.
+    pub fn push(&mut self, value: T) -> &mut T {
+        unsafe { self.emplace_push_with(|this| overwrite(this, value)) }
+    }
+
+    /// Appends a value by [`DefaultInPlace`] to the end.
+    ///
+    /// # Panics
+    /// Panics if the vector is full.
+    pub fn emplace_push_default(&mut self) -> &mut T
+    where
+        T: DefaultInPlace,
+    {
+        unsafe { self.emplace_push_with(T::default_in_place) }
+    }
+
+    /// Appends `count` elements, which are initialized by `f` with their indexes from 0.
+    ///
+    /// If `f` panics, every element initialized by `f` will be dropped.
+    ///
+    /// # Safety
+    /// `f` must initialize `T` correctly every time.
+    ///
+    /// # Panics
+    /// Panics if the vector does not have room for `count` elements.
+    pub unsafe fn extend_with(
+        &mut self,
+        count: usize,
+        f: impl FnMut(usize, &mut MaybeUninit<T>),
+    ) -> &mut [T] {
+        let start = self.len;
+        assert!(count <= N - start, "the vector is full");
+        init_slice_with(&mut self.slots()[start..start + count], f);
+        self.len += count;
+        &mut self.as_mut_slice()[start..]
+    }
+
+    /// Removes the last element and returns it.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        self.len -= 1;
+        let len = self.len;
+        Some(unsafe { self.slots()[len].assume_init_read() })
+    }
+
+    /// Drops the elements after `len`.
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+        let tail = ptr::addr_of_mut!(self.as_mut_slice()[len..]);
+        // the length is updated first, in case a drop panics
+        self.len = len;
+        unsafe { ptr::drop_in_place(tail) }
+    }
+
+    /// Drops every element.
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Returns the elements.
+    #[must_use]
+    pub const fn as_slice(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.buf.as_ptr().cast(), self.len) }
+    }
+
+    /// Returns the elements.
+    #[must_use]
+    #[allow(clippy::missing_const_for_fn)] // MSRV
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.buf.as_mut_ptr().cast(), self.len) }
+    }
+}
+
+impl<T, const N: usize> Default for InlineVec<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+unsafe impl<T, const N: usize> DefaultInPlace for InlineVec<T, N> {
+    #[inline]
+    fn default_in_place(this: &mut MaybeUninit<Self>) {
+        // the elements are left uninitialized
+        unsafe { ptr::addr_of_mut!((*this.as_mut_ptr()).len).write(0) }
+    }
+}
+
+// a zeroed vector is empty
+unsafe impl<T, const N: usize> Zeroable for InlineVec<T, N> {}
+
+impl<T, const N: usize> Deref for InlineVec<T, N> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, const N: usize> DerefMut for InlineVec<T, N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T, const N: usize> Drop for InlineVec<T, N> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for InlineVec<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
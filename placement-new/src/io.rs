@@ -0,0 +1,143 @@
+use crate::guard::guard_on_unwind;
+use crate::SlicePlace;
+
+use core::mem::MaybeUninit;
+use core::ptr;
+use core::slice;
+use rust_alloc::alloc::Layout;
+
+use std::boxed::Box;
+use std::io::{self, Read};
+
+/// Reads exactly `buf.len()` bytes into an uninitialized buffer.
+///
+/// [`Read`] only accepts initialized buffers, so every byte is zeroed before it is read into,
+/// and the cost of zeroing is not avoided.
+/// The buffer is zeroed chunk by chunk, right before each chunk is read into,
+/// so a chunk is still in cache when it is read into, and bytes after an error are never zeroed.
+///
+/// # Errors
+/// + Returns [`io::ErrorKind::UnexpectedEof`] if the reader reaches EOF before the buffer is filled.
+/// + Returns any other error of `reader` except [`io::ErrorKind::Interrupted`], which is retried.
+///
+/// The content of `buf` is unspecified when an error is returned.
+///
+/// # Panics
+/// Panics if `reader` returns a length greater than the length of the given buffer.
+pub fn read_exact_uninit<'a, R: Read + ?Sized>(
+    reader: &mut R,
+    buf: &'a mut [MaybeUninit<u8>],
+) -> io::Result<&'a mut [u8]> {
+    const CHUNK_SIZE: usize = 8192;
+
+    let len = buf.len();
+    let ptr = buf.as_mut_ptr().cast::<u8>();
+    let mut filled: usize = 0;
+    let mut zeroed: usize = 0;
+
+    while filled < len {
+        if filled == zeroed {
+            let n = CHUNK_SIZE.min(len - zeroed);
+            unsafe { ptr.add(zeroed).write_bytes(0, n) };
+            zeroed += n;
+        }
+
+        let dst = unsafe { slice::from_raw_parts_mut(ptr.add(filled), zeroed - filled) };
+        match reader.read(dst) {
+            Ok(0) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "failed to fill whole buffer",
+                ))
+            }
+            Ok(n) => {
+                assert!(n <= dst.len(), "the reader returned an invalid length");
+                filled += n;
+            }
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(unsafe { slice::from_raw_parts_mut(ptr, len) })
+}
+
+/// A place of bytes which can be read from a reader.
+pub trait ReadPlace: SlicePlace<u8> + Sized {
+    /// Reads exactly `len` bytes from `reader` into a new place.
+    ///
+    /// The bytes are zeroed before they are read into, as [`read_exact_uninit`] does.
+    ///
+    /// # Errors
+    /// Returns the error of [`read_exact_uninit`]. The memory is freed in this case.
+    fn emplace_from_reader<R: Read + ?Sized>(reader: &mut R, len: usize) -> io::Result<Self> {
+        unsafe { Self::try_emplace_with(len, |buf| read_exact_uninit(reader, buf).map(|_| ())) }
+    }
+}
+
+impl<P: SlicePlace<u8>> ReadPlace for P {}
+
+/// A fixed header followed by a variable-length body, stored in one allocation.
+#[derive(Debug)]
+#[repr(C)]
+pub struct HeaderSlice<H> {
+    /// The header.
+    pub header: H,
+    /// The body.
+    pub body: [u8],
+}
+
+impl<H> HeaderSlice<H> {
+    fn layout(len: usize) -> Layout {
+        let (layout, _) = Layout::new::<H>()
+            .extend(Layout::array::<u8>(len).expect("invalid layout"))
+            .expect("invalid layout");
+        layout.pad_to_align()
+    }
+
+    /// Reads a header and a body of the length given by the header.
+    ///
+    /// `read_header` reads the header and returns it with the length of the body.
+    /// The length is trusted, so `read_header` should reject unreasonable values.
+    ///
+    /// The body is read straight into the allocation, and zeroed chunk by chunk before it,
+    /// as [`read_exact_uninit`] does.
+    /// If reading the body fails or panics, the header is dropped and the memory is freed.
+    ///
+    /// # Errors
+    /// Returns the error of `read_header` or [`read_exact_uninit`].
+    ///
+    /// # Panics
+    /// Panics if the length is too large to be allocated.
+    pub fn emplace_from_reader<R: Read + ?Sized>(
+        reader: &mut R,
+        read_header: impl FnOnce(&mut R) -> io::Result<(H, usize)>,
+    ) -> io::Result<Box<Self>> {
+        let (header, len) = read_header(reader)?;
+        let layout = Self::layout(len);
+
+        unsafe {
+            let ptr = crate::try_emplace_with(layout, |ptr| {
+                let this = Self::from_raw_parts(ptr.cast(), len);
+                let header_ptr = ptr::addr_of_mut!((*this).header);
+                header_ptr.write(header);
+
+                let body = ptr::addr_of_mut!((*this).body) as *mut [MaybeUninit<u8>];
+                let ret = guard_on_unwind(
+                    || read_exact_uninit(reader, &mut *body),
+                    || ptr::drop_in_place(header_ptr),
+                );
+                if ret.is_err() {
+                    ptr::drop_in_place(header_ptr);
+                }
+                ret.map(|_| ())
+            })?;
+            Ok(Box::from_raw(Self::from_raw_parts(ptr.cast(), len)))
+        }
+    }
+
+    #[allow(clippy::missing_const_for_fn)] // MSRV
+    fn from_raw_parts(ptr: *mut u8, len: usize) -> *mut Self {
+        ptr::slice_from_raw_parts_mut(ptr, len) as *mut Self
+    }
+}
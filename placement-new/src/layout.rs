@@ -0,0 +1,46 @@
+/// The layout of a field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldInfo {
+    /// The name of the field, or its index for a tuple field.
+    pub name: &'static str,
+    /// The offset of the field from the start of the whole type.
+    pub offset: usize,
+    /// The size of the field.
+    pub size: usize,
+    /// The alignment of the field.
+    pub align: usize,
+    /// The type of the field, as it is written in the source.
+    pub type_name: &'static str,
+}
+
+/// The layout of an enum variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VariantInfo {
+    /// The name of the variant.
+    pub name: &'static str,
+    /// The fields of the variant, whose offsets are relative to the start of the enum.
+    pub fields: &'static [FieldInfo],
+}
+
+/// The layout of an enum tag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TagInfo {
+    /// The offset of the tag.
+    pub offset: usize,
+    /// The size of the tag.
+    pub size: usize,
+}
+
+/// A type with field layout metadata.
+///
+/// It can be derived for the types which derive [`UninitProject`](crate::UninitProject).
+pub trait LayoutInfo {
+    /// The fields of a struct. It is empty for an enum.
+    const LAYOUT: &'static [FieldInfo];
+
+    /// The variants of an enum. It is empty for a struct.
+    const VARIANTS: &'static [VariantInfo] = &[];
+
+    /// The tag of an enum. It is `None` for a struct.
+    const TAG: Option<TagInfo> = None;
+}
@@ -13,19 +13,66 @@
 #[cfg(feature = "alloc")]
 extern crate alloc as rust_alloc;
 
+#[cfg(feature = "std")]
+extern crate std;
+
 #[cfg(feature = "alloc")]
 mod alloc;
 
 mod array;
 
+mod clone;
+
+#[cfg(feature = "serde")]
+mod de;
+
+mod default;
+
+mod from_bytes;
+
 mod guard;
 
+mod inline;
+
+mod inline_vec;
+
+#[cfg(feature = "std")]
+mod io;
+
+mod layout;
+
+#[cfg(feature = "alloc")]
+mod map;
+
+#[cfg(all(feature = "mmap", target_os = "linux"))]
+mod mmap;
+
+#[cfg(target_has_atomic = "8")]
+mod once;
+
+#[cfg(feature = "std")]
+mod par;
+
 mod place;
 
+#[cfg(feature = "alloc")]
+mod pool;
+
+#[cfg(feature = "alloc")]
+mod recycle;
+
+mod reinit;
+
 mod slice;
 
+mod string;
+
 mod uninit;
 
+mod uninit_ptr;
+
+mod zeroable;
+
 #[doc(hidden)]
 pub mod __private;
 
@@ -34,11 +81,61 @@ pub use self::alloc::*;
 
 pub use self::array::*;
 
+pub use self::clone::*;
+
+#[cfg(feature = "serde")]
+pub use self::de::*;
+
+pub use self::default::*;
+
+pub use self::from_bytes::*;
+
+pub use self::inline::*;
+
+pub use self::inline_vec::*;
+
+#[cfg(feature = "std")]
+pub use self::io::*;
+
+pub use self::layout::*;
+
+#[cfg(feature = "alloc")]
+pub use self::map::*;
+
+#[cfg(all(feature = "mmap", target_os = "linux"))]
+pub use self::mmap::*;
+
+#[cfg(target_has_atomic = "8")]
+pub use self::once::*;
+
+#[cfg(feature = "std")]
+pub use self::par::*;
+
 pub use self::place::*;
 
+#[cfg(feature = "alloc")]
+pub use self::pool::*;
+
+#[cfg(feature = "alloc")]
+pub use self::recycle::*;
+
+pub use self::reinit::*;
+
 pub use self::slice::*;
 
+pub use self::string::*;
+
 pub use self::uninit::*;
 
+pub use self::uninit_ptr::*;
+
+pub use self::zeroable::*;
+
 #[cfg(feature = "derive")]
-pub use placement_new_derive::UninitProject;
+pub use placement_new_derive::{
+    CloneInto, DefaultInPlace, FromBytesInPlace, LayoutInfo, UninitProject, UninitPtrProject,
+    Zeroable,
+};
+
+#[cfg(all(feature = "derive", feature = "serde"))]
+pub use placement_new_derive::DeserializeInPlace;
@@ -0,0 +1,132 @@
+use crate::guard::guard_on_unwind;
+use crate::{init_slice_with, overwrite, SlicePlace};
+
+use core::alloc::Layout;
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::{ptr, slice};
+
+use rust_alloc::boxed::Box;
+use rust_alloc::vec::Vec;
+
+/// A container whose elements can be converted in place.
+pub trait MapInPlace<T, U> {
+    /// The container of `U`.
+    type Output;
+
+    /// Converts every element by `f`, reusing the allocation when the layouts of `T` and `U` are the same.
+    /// Otherwise, a new allocation is made and the old one is freed.
+    ///
+    /// `f` takes two arguments: the element's index and value.
+    ///
+    /// If `f` panics, every `U` returned by `f` and every `T` not yet passed to `f` will be dropped.
+    fn map_in_place(self, f: impl FnMut(usize, T) -> U) -> Self::Output;
+}
+
+#[inline]
+fn is_same_layout<T, U>() -> bool {
+    Layout::new::<T>() == Layout::new::<U>()
+}
+
+/// Drops every `T` in `src[*consumed..]` if `g` panics.
+unsafe fn guard_suffix<T, R>(
+    src: *mut [MaybeUninit<T>],
+    consumed: *mut usize,
+    g: impl FnOnce() -> R,
+) -> R {
+    guard_on_unwind(g, || {
+        let suffix = (&mut *src).get_unchecked_mut(*consumed..);
+        for this in suffix {
+            ptr::drop_in_place(this.as_mut_ptr());
+        }
+    })
+}
+
+/// Converts every `T` in `slice` to `U` in place.
+///
+/// # Safety
+/// + Every element of `slice` must be initialized.
+/// + The layouts of `T` and `U` must be the same.
+unsafe fn map_slice_in_place<T, U>(slice: &mut [MaybeUninit<T>], mut f: impl FnMut(usize, T) -> U) {
+    let mut consumed: usize = 0;
+
+    let consumed = ptr::addr_of_mut!(consumed);
+    let src: *mut [MaybeUninit<T>] = slice;
+    let dst: *mut [MaybeUninit<U>] = src as *mut [MaybeUninit<U>];
+
+    guard_suffix(src, consumed, || {
+        init_slice_with(&mut *dst, |idx, this| {
+            let value = this.as_ptr().cast::<T>().read();
+            *consumed = idx + 1;
+            overwrite(this, f(idx, value));
+        });
+    });
+}
+
+/// Moves every `T` in `src` to `dst` as `U`.
+///
+/// # Safety
+/// + Every element of `src` must be initialized.
+/// + `src` and `dst` must have the same length.
+unsafe fn map_slice_to<T, U>(
+    src: &mut [MaybeUninit<T>],
+    dst: &mut [MaybeUninit<U>],
+    mut f: impl FnMut(usize, T) -> U,
+) {
+    let mut consumed: usize = 0;
+
+    let consumed = ptr::addr_of_mut!(consumed);
+    let src: *mut [MaybeUninit<T>] = src;
+
+    guard_suffix(src, consumed, || {
+        init_slice_with(dst, |idx, this| {
+            let value = (&*src).get_unchecked(idx).as_ptr().read();
+            *consumed = idx + 1;
+            overwrite(this, f(idx, value));
+        });
+    });
+}
+
+impl<T, U> MapInPlace<T, U> for Box<[T]> {
+    type Output = Box<[U]>;
+
+    fn map_in_place(self, f: impl FnMut(usize, T) -> U) -> Self::Output {
+        let len = self.len();
+        let ptr = Self::into_raw(self);
+        unsafe {
+            // the allocation is freed by `src` if `f` panics
+            let mut src: Box<[MaybeUninit<T>]> = Box::from_raw(ptr as *mut [MaybeUninit<T>]);
+
+            if is_same_layout::<T, U>() {
+                map_slice_in_place(&mut src, f);
+                Box::from_raw(Box::into_raw(src) as *mut [U])
+            } else {
+                Box::emplace_with(len, |dst| map_slice_to(&mut src, dst, f))
+            }
+        }
+    }
+}
+
+impl<T, U> MapInPlace<T, U> for Vec<T> {
+    type Output = Vec<U>;
+
+    fn map_in_place(self, f: impl FnMut(usize, T) -> U) -> Self::Output {
+        let mut this = ManuallyDrop::new(self);
+        let (ptr, len, cap) = (this.as_mut_ptr(), this.len(), this.capacity());
+        unsafe {
+            // the allocation is freed by `src` if `f` panics
+            let mut src: Vec<MaybeUninit<T>> = Vec::from_raw_parts(ptr.cast(), len, cap);
+
+            if is_same_layout::<T, U>() {
+                map_slice_in_place(&mut src, f);
+                let mut src = ManuallyDrop::new(src);
+                Vec::from_raw_parts(src.as_mut_ptr().cast(), len, cap)
+            } else {
+                let mut dst: Vec<U> = Vec::with_capacity(len);
+                let spare = slice::from_raw_parts_mut(dst.as_mut_ptr().cast(), len);
+                map_slice_to(&mut src, spare, f);
+                dst.set_len(len);
+                dst
+            }
+        }
+    }
+}
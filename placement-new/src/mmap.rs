@@ -0,0 +1,288 @@
+use crate::guard::{guard_on_unwind, Guard};
+use crate::{FromBytesInPlace, Place, PlaceTarget, Zeroable};
+
+use core::convert::TryFrom;
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem;
+use core::ops::{Deref, DerefMut};
+use core::ptr::{self, NonNull};
+use core::slice;
+
+use rust_alloc::boxed::Box;
+
+use std::alloc::{handle_alloc_error, Layout};
+use std::fs::File;
+use std::io;
+use std::os::unix::io::AsRawFd;
+
+/// A value stored in memory mapped by `mmap`, which is either anonymous or backed by a file.
+///
+/// Anonymous pages are zeroed lazily by the kernel,
+/// so a huge zeroed value costs nothing until its pages are touched.
+///
+/// A file-backed value is written back to its file.
+/// The destructor of the value runs before the memory is unmapped.
+pub struct MmapBox<T: ?Sized> {
+    ptr: NonNull<T>,
+    // the length of the mapping, which is zero if nothing is mapped
+    map_len: usize,
+    _marker: PhantomData<T>,
+}
+
+unsafe impl<T: ?Sized + Send> Send for MmapBox<T> {}
+unsafe impl<T: ?Sized + Sync> Sync for MmapBox<T> {}
+
+fn page_size() -> usize {
+    let size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    usize::try_from(size).expect("invalid page size")
+}
+
+/// Maps `layout.size()` bytes, or returns a dangling pointer if the size is zero.
+unsafe fn map(layout: Layout, flags: libc::c_int, fd: libc::c_int) -> io::Result<*mut ()> {
+    if layout.size() == 0 {
+        return Ok(layout.align() as *mut ());
+    }
+    assert!(layout.align() <= page_size(), "the value is over-aligned");
+    let prot = libc::PROT_READ | libc::PROT_WRITE;
+    let ptr = libc::mmap(ptr::null_mut(), layout.size(), prot, flags, fd, 0);
+    if ptr == libc::MAP_FAILED {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(ptr.cast())
+}
+
+unsafe fn unmap(ptr: *mut (), map_len: usize) {
+    if map_len != 0 {
+        libc::munmap(ptr.cast(), map_len);
+    }
+}
+
+fn size_mismatch() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        "the file size does not match the value",
+    )
+}
+
+fn file_len(file: &File) -> io::Result<usize> {
+    usize::try_from(file.metadata()?.len()).map_err(|_| size_mismatch())
+}
+
+/// Validates `len` values of `T` in `bytes`.
+///
+/// Every value is read into the same heap slot and dropped.
+fn validate_from_bytes<T: FromBytesInPlace>(bytes: &[u8], len: usize) -> io::Result<()> {
+    let size = mem::size_of::<T>();
+    let mut tmp = Box::<T>::new_uninit();
+    for idx in 0..len {
+        T::from_bytes_in_place(&bytes[idx * size..], &mut tmp)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.add_offset(idx * size)))?;
+        unsafe { tmp.assume_init_drop() }
+    }
+    Ok(())
+}
+
+impl<T: ?Sized + PlaceTarget> MmapBox<T> {
+    /// Initializes the mapped memory by `f`, and unmaps it if `f` fails.
+    unsafe fn try_init_with<E>(
+        ptr: *mut (),
+        meta: T::Metadata,
+        map_len: usize,
+        f: impl FnOnce(*mut T) -> Result<(), E>,
+    ) -> Result<Self, E> {
+        let this = T::from_raw_parts(ptr, meta);
+        match guard_on_unwind(|| f(this), || unmap(ptr, map_len)) {
+            Ok(()) => Ok(Self {
+                ptr: NonNull::new_unchecked(this),
+                map_len,
+                _marker: PhantomData,
+            }),
+            Err(e) => {
+                unmap(ptr, map_len);
+                Err(e)
+            }
+        }
+    }
+
+    /// Maps `file` as a value with `meta`, which is checked by `f` before it is owned.
+    ///
+    /// If `zeroed` is true, an empty file is extended with zero to the size of the value.
+    unsafe fn try_map_file_with(
+        file: &File,
+        meta: T::Metadata,
+        zeroed: bool,
+        f: impl FnOnce(*mut T) -> io::Result<()>,
+    ) -> io::Result<Self> {
+        let layout = T::layout(meta);
+        let len = file_len(file)?;
+        if len != layout.size() {
+            if !(zeroed && len == 0) {
+                return Err(size_mismatch());
+            }
+            file.set_len(layout.size() as u64)?;
+        }
+        let ptr = map(layout, libc::MAP_SHARED, file.as_raw_fd())?;
+        Self::try_init_with(ptr, meta, layout.size(), f)
+    }
+
+    /// Writes the changes of a file-backed value to its file.
+    ///
+    /// It does nothing for an anonymous value.
+    ///
+    /// # Errors
+    /// Returns the error of `msync`.
+    pub fn flush(&self) -> io::Result<()> {
+        if self.map_len == 0 {
+            return Ok(());
+        }
+        let ptr = self.ptr.as_ptr().cast::<libc::c_void>();
+        if unsafe { libc::msync(ptr, self.map_len, libc::MS_SYNC) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+}
+
+impl<T> MmapBox<T> {
+    /// Maps a file containing a `T`.
+    ///
+    /// An empty file is extended with zero to the size of `T`.
+    ///
+    /// # Errors
+    /// Returns an error if the file size is neither zero nor the size of `T`,
+    /// or if the file cannot be mapped.
+    ///
+    /// # Safety
+    /// + If the file is not empty, it must contain a valid `T`,
+    ///   such as one written by an earlier `MmapBox<T>`.
+    /// + The file must not be modified by others while the value is alive.
+    pub unsafe fn map_file_zeroed(file: &File) -> io::Result<Self>
+    where
+        T: Zeroable,
+    {
+        Self::try_map_file_with(file, (), true, |_| Ok(()))
+    }
+
+    /// Maps a file containing a `T`, which is validated by [`FromBytesInPlace`].
+    ///
+    /// The bytes are validated by reading them into a temporary heap slot.
+    ///
+    /// # Errors
+    /// + Returns [`io::ErrorKind::InvalidData`] if the file size is not the size of `T`,
+    ///   or if the bytes are invalid.
+    /// + Returns an error if the file cannot be mapped.
+    ///
+    /// # Safety
+    /// The file must not be modified by others while the value is alive.
+    pub unsafe fn map_file_from_bytes(file: &File) -> io::Result<Self>
+    where
+        T: FromBytesInPlace,
+    {
+        Self::try_map_file_with(file, (), false, |ptr| {
+            let bytes = slice::from_raw_parts(ptr.cast::<u8>(), mem::size_of::<T>());
+            validate_from_bytes::<T>(bytes, 1)
+        })
+    }
+}
+
+impl<T> MmapBox<[T]> {
+    /// Maps a file containing `len` elements of `T`.
+    ///
+    /// An empty file is extended with zero to the size of the slice.
+    ///
+    /// # Errors
+    /// Returns an error if the file size is neither zero nor the size of the slice,
+    /// or if the file cannot be mapped.
+    ///
+    /// # Safety
+    /// + If the file is not empty, it must contain valid elements,
+    ///   such as ones written by an earlier `MmapBox<[T]>`.
+    /// + The file must not be modified by others while the value is alive.
+    pub unsafe fn map_file_zeroed_slice(file: &File, len: usize) -> io::Result<Self>
+    where
+        T: Zeroable,
+    {
+        Self::try_map_file_with(file, len, true, |_| Ok(()))
+    }
+
+    /// Maps a file containing elements of `T`, which are validated by [`FromBytesInPlace`].
+    ///
+    /// The number of elements is determined by the file size.
+    ///
+    /// # Errors
+    /// + Returns [`io::ErrorKind::InvalidData`] if the file size is not a multiple of the size of `T`,
+    ///   or if the bytes are invalid.
+    /// + Returns an error if the file cannot be mapped.
+    ///
+    /// # Safety
+    /// The file must not be modified by others while the value is alive.
+    pub unsafe fn map_file_from_bytes_slice(file: &File) -> io::Result<Self>
+    where
+        T: FromBytesInPlace,
+    {
+        let size = mem::size_of::<T>();
+        let file_len = file_len(file)?;
+        let len = match file_len.checked_div(size) {
+            Some(len) if len * size == file_len => len,
+            _ if file_len == 0 => 0,
+            _ => return Err(size_mismatch()),
+        };
+        Self::try_map_file_with(file, len, false, |ptr| {
+            let bytes = slice::from_raw_parts(ptr.cast::<u8>(), file_len);
+            validate_from_bytes::<T>(bytes, len)
+        })
+    }
+}
+
+impl<T: ?Sized + PlaceTarget> Place<T> for MmapBox<T> {
+    unsafe fn try_emplace_raw_with<E>(
+        meta: T::Metadata,
+        f: impl FnOnce(*mut T) -> Result<(), E>,
+    ) -> Result<Self, E> {
+        let layout = T::layout(meta);
+        let flags = libc::MAP_PRIVATE | libc::MAP_ANONYMOUS;
+        let ptr = map(layout, flags, -1).unwrap_or_else(|_| handle_alloc_error(layout));
+        Self::try_init_with(ptr, meta, layout.size(), f)
+    }
+
+    // anonymous pages are already zeroed
+    unsafe fn try_emplace_zeroed_raw_with<E>(
+        meta: T::Metadata,
+        f: impl FnOnce(*mut T) -> Result<(), E>,
+    ) -> Result<Self, E> {
+        Self::try_emplace_raw_with(meta, f)
+    }
+}
+
+impl<T: ?Sized> Deref for MmapBox<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for MmapBox<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { self.ptr.as_mut() }
+    }
+}
+
+impl<T: ?Sized> Drop for MmapBox<T> {
+    fn drop(&mut self) {
+        let ptr = self.ptr.as_ptr();
+        let map_len = self.map_len;
+        // the memory is unmapped even if the destructor panics
+        let _guard = Guard::new(move || unsafe { unmap(ptr.cast(), map_len) });
+        unsafe { ptr::drop_in_place(ptr) }
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for MmapBox<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
@@ -0,0 +1,267 @@
+use crate::guard::guard_on_unwind;
+use crate::{memset_zeroed, DefaultInPlace, Zeroable};
+
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::mem::MaybeUninit;
+use core::panic::{RefUnwindSafe, UnwindSafe};
+use core::sync::atomic::{AtomicU8, Ordering};
+
+const UNINIT: u8 = 0;
+const RUNNING: u8 = 1;
+const DONE: u8 = 2;
+const POISONED: u8 = 3;
+
+/// The state of a value which is initialized once.
+struct OnceState(AtomicU8);
+
+impl OnceState {
+    const fn new() -> Self {
+        Self(AtomicU8::new(UNINIT))
+    }
+
+    #[inline]
+    fn is_done(&self) -> bool {
+        self.0.load(Ordering::Acquire) == DONE
+    }
+
+    #[inline]
+    fn is_poisoned(&self) -> bool {
+        self.0.load(Ordering::Acquire) == POISONED
+    }
+
+    /// Calls `f` if no thread has called it, or waits for the thread which is calling it.
+    ///
+    /// If `f` panics, the state is poisoned.
+    fn call_once(&self, f: impl FnOnce()) {
+        loop {
+            match self.0.compare_exchange_weak(
+                UNINIT,
+                RUNNING,
+                Ordering::Acquire,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => {
+                    guard_on_unwind(f, || self.0.store(POISONED, Ordering::Release));
+                    self.0.store(DONE, Ordering::Release);
+                    return;
+                }
+                Err(DONE) => return,
+                Err(POISONED) => panic!("the initializer panicked before"),
+                Err(_) => wait(),
+            }
+        }
+    }
+}
+
+#[inline]
+fn wait() {
+    #[cfg(feature = "std")]
+    std::thread::yield_now();
+    #[cfg(not(feature = "std"))]
+    core::hint::spin_loop();
+}
+
+/// A slot which is initialized in place exactly once, usually in a `static`.
+///
+/// The value is never built on the stack, so it can hold huge tables.
+/// If the initializer panics, the slot is poisoned and every later access panics.
+///
+/// It is only available on targets with 8-bit atomics.
+pub struct StaticPlace<T> {
+    state: OnceState,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+unsafe impl<T: Send + Sync> Sync for StaticPlace<T> {}
+
+// a panic in the initializer poisons the slot
+impl<T: RefUnwindSafe + UnwindSafe> RefUnwindSafe for StaticPlace<T> {}
+
+impl<T> StaticPlace<T> {
+    /// Creates an uninitialized slot.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            state: OnceState::new(),
+            value: UnsafeCell::new(MaybeUninit::uninit()),
+        }
+    }
+
+    /// Returns the value if it is initialized.
+    #[inline]
+    pub fn get(&self) -> Option<&T> {
+        if self.state.is_done() {
+            Some(unsafe { (*self.value.get()).assume_init_ref() })
+        } else {
+            None
+        }
+    }
+
+    /// Returns whether the initializer panicked.
+    #[inline]
+    pub fn is_poisoned(&self) -> bool {
+        self.state.is_poisoned()
+    }
+
+    /// Returns the value, or initializes it by `f` if no thread has done it.
+    ///
+    /// If another thread is running its initializer, this call blocks until it finishes.
+    /// Calling it again inside `f` never returns.
+    ///
+    /// # Safety
+    /// `f` must initialize `T` correctly.
+    ///
+    /// # Panics
+    /// Panics if the slot is poisoned.
+    pub unsafe fn get_or_emplace_with(&self, f: impl FnOnce(&mut MaybeUninit<T>)) -> &T {
+        self.state.call_once(|| f(&mut *self.value.get()));
+        (*self.value.get()).assume_init_ref()
+    }
+
+    /// Returns the value, or initializes it by [`DefaultInPlace`].
+    ///
+    /// # Panics
+    /// Panics if the slot is poisoned.
+    pub fn get_or_emplace_default(&self) -> &T
+    where
+        T: DefaultInPlace,
+    {
+        unsafe { self.get_or_emplace_with(T::default_in_place) }
+    }
+
+    /// Returns the value, or initializes it with zero.
+    ///
+    /// # Panics
+    /// Panics if the slot is poisoned.
+    pub fn get_or_init_zeroed(&self) -> &T
+    where
+        T: Zeroable,
+    {
+        unsafe { self.get_or_emplace_with(memset_zeroed) }
+    }
+}
+
+impl<T> Default for StaticPlace<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for StaticPlace<T> {
+    fn drop(&mut self) {
+        if self.state.is_done() {
+            unsafe { self.value.get_mut().assume_init_drop() }
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for StaticPlace<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.get() {
+            Some(value) => f.debug_tuple("StaticPlace").field(value).finish(),
+            None => f.write_str("StaticPlace(<uninit>)"),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod alloc_impls {
+    use super::OnceState;
+    use crate::{DefaultInPlace, SinglePlace};
+
+    use core::cell::UnsafeCell;
+    use core::fmt;
+    use core::mem::MaybeUninit;
+    use core::ops::Deref;
+    use core::panic::{RefUnwindSafe, UnwindSafe};
+
+    use rust_alloc::boxed::Box;
+
+    /// A heap value which is initialized in place on first access.
+    ///
+    /// If the initializer panics, every later access panics.
+    ///
+    /// It is only available on targets with 8-bit atomics.
+    pub struct LazyBox<T, F = fn(&mut MaybeUninit<T>)> {
+        state: OnceState,
+        value: UnsafeCell<MaybeUninit<Box<T>>>,
+        init: F,
+    }
+
+    unsafe impl<T: Send + Sync, F: Sync> Sync for LazyBox<T, F> {}
+
+    // a panic in the initializer poisons the value
+    impl<T: RefUnwindSafe + UnwindSafe, F: RefUnwindSafe> RefUnwindSafe for LazyBox<T, F> {}
+
+    impl<T, F: Fn(&mut MaybeUninit<T>)> LazyBox<T, F> {
+        /// Creates a value which will be initialized by `init`.
+        ///
+        /// # Safety
+        /// `init` must initialize `T` correctly.
+        pub const unsafe fn new(init: F) -> Self {
+            Self {
+                state: OnceState::new(),
+                value: UnsafeCell::new(MaybeUninit::uninit()),
+                init,
+            }
+        }
+
+        /// Returns the value, and initializes it if no thread has done it.
+        ///
+        /// # Panics
+        /// Panics if the initializer panicked before.
+        pub fn force(this: &Self) -> &T {
+            this.state.call_once(|| unsafe {
+                let boxed = Box::emplace_with(&this.init);
+                (*this.value.get()).write(boxed);
+            });
+            unsafe { (*this.value.get()).assume_init_ref() }
+        }
+
+        /// Returns the value if it is initialized.
+        pub fn get(this: &Self) -> Option<&T> {
+            if this.state.is_done() {
+                Some(unsafe { (*this.value.get()).assume_init_ref() })
+            } else {
+                None
+            }
+        }
+    }
+
+    impl<T: DefaultInPlace> LazyBox<T> {
+        /// Creates a value which will be initialized by [`DefaultInPlace`].
+        #[must_use]
+        pub const fn new_default() -> Self {
+            unsafe { Self::new(T::default_in_place) }
+        }
+    }
+
+    impl<T, F: Fn(&mut MaybeUninit<T>)> Deref for LazyBox<T, F> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            Self::force(self)
+        }
+    }
+
+    impl<T, F> Drop for LazyBox<T, F> {
+        fn drop(&mut self) {
+            if self.state.is_done() {
+                unsafe { self.value.get_mut().assume_init_drop() }
+            }
+        }
+    }
+
+    impl<T: fmt::Debug, F: Fn(&mut MaybeUninit<T>)> fmt::Debug for LazyBox<T, F> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match Self::get(self) {
+                Some(value) => f.debug_tuple("LazyBox").field(value).finish(),
+                None => f.write_str("LazyBox(<uninit>)"),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub use self::alloc_impls::LazyBox;
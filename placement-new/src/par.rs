@@ -0,0 +1,74 @@
+use crate::init_slice_with;
+
+use core::mem::MaybeUninit;
+use core::ptr;
+
+use std::panic;
+use std::thread;
+use std::vec::Vec;
+
+/// Initializes a slice in parallel.
+///
+/// The slice is split into at most `threads` chunks, each of which is initialized by a scoped thread.
+///
+/// `f` takes two arguments: the element's index and pointer.
+///
+/// If `f` panics in any thread, every `T` initialized by `f` in every chunk
+/// will be dropped before the panic is propagated.
+///
+/// # Safety
+/// + `f` must initialize `T` correctly every time.
+pub unsafe fn par_init_slice_with<T: Send>(
+    slice: &mut [MaybeUninit<T>],
+    threads: usize,
+    f: impl Fn(usize, &mut MaybeUninit<T>) + Sync,
+) -> &mut [T] {
+    let len = slice.len();
+    if threads <= 1 || len <= 1 {
+        return init_slice_with(slice, f);
+    }
+
+    #[allow(clippy::manual_div_ceil)] // MSRV
+    let chunk_size = (len + threads - 1) / threads;
+    let f = &f;
+
+    let results: Vec<thread::Result<()>> = thread::scope(|s| {
+        let handles: Vec<_> = slice
+            .chunks_mut(chunk_size)
+            .enumerate()
+            .map(|(i, chunk)| {
+                let base = i * chunk_size;
+                s.spawn(move || unsafe {
+                    init_slice_with(chunk, |idx, this| f(base + idx, this));
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(thread::ScopedJoinHandle::join)
+            .collect()
+    });
+
+    if results.iter().any(Result::is_err) {
+        let mut payload = None;
+        for (chunk, result) in slice.chunks_mut(chunk_size).zip(results) {
+            match result {
+                Ok(()) => ptr::drop_in_place(as_mut_ptr(chunk)),
+                // the chunk has been cleaned up by its own thread
+                Err(e) => {
+                    payload.get_or_insert(e);
+                }
+            }
+        }
+        if let Some(payload) = payload {
+            panic::resume_unwind(payload)
+        }
+    }
+
+    &mut *as_mut_ptr(slice)
+}
+
+#[allow(clippy::missing_const_for_fn)] // MSRV
+fn as_mut_ptr<T>(slice: &mut [MaybeUninit<T>]) -> *mut [T] {
+    ptr::slice_from_raw_parts_mut(slice.as_mut_ptr().cast(), slice.len())
+}
@@ -1,23 +1,385 @@
+use crate::{
+    CloneInto, DefaultInPlace, FromBytesError, FromBytesInPlace, UninitProjectDst, Zeroable,
+};
+
+use core::alloc::Layout;
+use core::convert::Infallible;
 use core::mem::MaybeUninit;
+use core::ptr;
+
+/// A type which can be the target of a [`Place`], either sized or not.
+///
+/// # Safety
+/// + `layout` must return the layout of a value with `meta`.
+/// + `from_raw_parts` must return a pointer to a value with `meta`, at the address of `ptr`.
+pub unsafe trait PlaceTarget {
+    /// The pointer metadata, which is `()` for sized types and the length for slices.
+    type Metadata: Copy;
+
+    /// Returns the layout of a value with `meta`.
+    fn layout(meta: Self::Metadata) -> Layout;
+
+    /// Creates a pointer to a value with `meta`.
+    fn from_raw_parts(ptr: *mut (), meta: Self::Metadata) -> *mut Self;
+}
+
+unsafe impl<T> PlaceTarget for T {
+    type Metadata = ();
+
+    #[inline]
+    fn layout((): ()) -> Layout {
+        Layout::new::<T>()
+    }
+
+    #[inline]
+    fn from_raw_parts(ptr: *mut (), (): ()) -> *mut T {
+        ptr.cast()
+    }
+}
+
+unsafe impl<T> PlaceTarget for [T] {
+    type Metadata = usize;
+
+    #[inline]
+    fn layout(len: usize) -> Layout {
+        Layout::array::<T>(len).expect("invalid layout")
+    }
+
+    #[inline]
+    fn from_raw_parts(ptr: *mut (), len: usize) -> *mut [T] {
+        ptr::slice_from_raw_parts_mut(ptr.cast(), len)
+    }
+}
+
+unsafe impl PlaceTarget for str {
+    type Metadata = usize;
+
+    #[inline]
+    fn layout(len: usize) -> Layout {
+        Layout::array::<u8>(len).expect("invalid layout")
+    }
+
+    #[inline]
+    fn from_raw_parts(ptr: *mut (), len: usize) -> *mut Self {
+        <[u8]>::from_raw_parts(ptr, len) as *mut Self
+    }
+}
+
+/// A place containing a value of `T`, which may be unsized.
+///
+/// [`SinglePlace`] and [`SlicePlace`] are implemented for every `Place`.
+///
+/// `Box` implements it for every [`PlaceTarget`].
+/// `Rc` and `Arc` implement it only for `T`, `[T]` and `str`,
+/// because the layout of their allocations is not public.
+pub trait Place<T: ?Sized + PlaceTarget>: Sized {
+    /// If `f` returns an error, the memory is freed and the error is returned.
+    ///
+    /// `f` receives a pointer to the uninitialized value with `meta`.
+    ///
+    /// # Errors
+    /// Returns the error of `f`.
+    ///
+    /// # Safety
+    /// `f` must initialize the value correctly if it returns `Ok`.
+    unsafe fn try_emplace_raw_with<E>(
+        meta: T::Metadata,
+        f: impl FnOnce(*mut T) -> Result<(), E>,
+    ) -> Result<Self, E>;
+
+    /// The memory is filled with zero before `f` is called.
+    ///
+    /// # Errors
+    /// Returns the error of `f`.
+    ///
+    /// # Safety
+    /// `f` must initialize the value correctly if it returns `Ok`.
+    unsafe fn try_emplace_zeroed_raw_with<E>(
+        meta: T::Metadata,
+        f: impl FnOnce(*mut T) -> Result<(), E>,
+    ) -> Result<Self, E>;
+
+    /// # Safety
+    /// `f` must initialize the value correctly.
+    unsafe fn emplace_raw_with(meta: T::Metadata, f: impl FnOnce(*mut T)) -> Self {
+        let ret = Self::try_emplace_raw_with(meta, |ptr| {
+            f(ptr);
+            Ok::<(), Infallible>(())
+        });
+        match ret {
+            Ok(place) => place,
+            Err(e) => match e {},
+        }
+    }
+
+    /// The memory is filled with zero before `f` is called.
+    ///
+    /// # Safety
+    /// `f` must initialize the value correctly.
+    unsafe fn emplace_zeroed_raw_with(meta: T::Metadata, f: impl FnOnce(*mut T)) -> Self {
+        let ret = Self::try_emplace_zeroed_raw_with(meta, |ptr| {
+            f(ptr);
+            Ok::<(), Infallible>(())
+        });
+        match ret {
+            Ok(place) => place,
+            Err(e) => match e {},
+        }
+    }
+}
 
 /// A place containing a single value.
+///
+/// It is implemented for every [`Place<T>`](Place).
 pub trait SinglePlace<T> {
     /// # Safety
     /// `f` must initialize `T` correctly.
     unsafe fn emplace_with(f: impl FnOnce(&mut MaybeUninit<T>)) -> Self;
 
+    /// The memory is filled with zero before `f` is called.
+    ///
     /// # Safety
     /// `f` must initialize `T` correctly.
     unsafe fn emplace_zeroed_with(f: impl FnOnce(&mut MaybeUninit<T>)) -> Self;
+
+    /// If `f` returns an error, the memory is freed and the error is returned.
+    ///
+    /// # Errors
+    /// Returns the error of `f`.
+    ///
+    /// # Safety
+    /// `f` must initialize `T` correctly if it returns `Ok`.
+    unsafe fn try_emplace_with<E>(
+        f: impl FnOnce(&mut MaybeUninit<T>) -> Result<(), E>,
+    ) -> Result<Self, E>
+    where
+        Self: Sized;
+
+    /// Creates a zeroed `T`.
+    #[must_use]
+    fn new_zeroed_safe() -> Self
+    where
+        Self: Sized,
+        T: Zeroable,
+    {
+        unsafe { Self::emplace_zeroed_with(|_| {}) }
+    }
+
+    /// Creates a `T` by [`DefaultInPlace`].
+    #[must_use]
+    fn emplace_default() -> Self
+    where
+        Self: Sized,
+        T: DefaultInPlace,
+    {
+        unsafe { Self::emplace_with(T::default_in_place) }
+    }
+
+    /// Creates a `T` by [`CloneInto`].
+    #[must_use]
+    fn emplace_clone(src: &T) -> Self
+    where
+        Self: Sized,
+        T: CloneInto,
+    {
+        unsafe { Self::emplace_with(|dst| src.clone_into_uninit(dst)) }
+    }
+
+    /// Reads a `T` from bytes by [`FromBytesInPlace`].
+    ///
+    /// If the bytes are invalid, the memory is freed and the error is returned.
+    ///
+    /// # Errors
+    /// Returns an error if `bytes` is too short or contains an invalid value.
+    fn emplace_from_bytes(bytes: &[u8]) -> Result<Self, FromBytesError>
+    where
+        Self: Sized,
+        T: FromBytesInPlace,
+    {
+        unsafe { Self::try_emplace_with(|this| T::from_bytes_in_place(bytes, this)) }
+    }
+
+    /// Creates a `T` by [`DeserializeInPlace`](crate::DeserializeInPlace).
+    ///
+    /// If the deserialization fails, the memory is freed and the error is returned.
+    ///
+    /// # Errors
+    /// Returns the error of `deserializer`.
+    #[cfg(feature = "serde")]
+    fn emplace_deserialize<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        Self: Sized,
+        T: crate::DeserializeInPlace<'de>,
+        D: serde::Deserializer<'de>,
+    {
+        unsafe { Self::try_emplace_with(|this| T::deserialize_in_place(deserializer, this)) }
+    }
+}
+
+impl<T, P: Place<T>> SinglePlace<T> for P {
+    #[inline]
+    unsafe fn emplace_with(f: impl FnOnce(&mut MaybeUninit<T>)) -> Self {
+        Self::emplace_raw_with((), |ptr| f(&mut *ptr.cast()))
+    }
+
+    #[inline]
+    unsafe fn emplace_zeroed_with(f: impl FnOnce(&mut MaybeUninit<T>)) -> Self {
+        Self::emplace_zeroed_raw_with((), |ptr| f(&mut *ptr.cast()))
+    }
+
+    #[inline]
+    unsafe fn try_emplace_with<E>(
+        f: impl FnOnce(&mut MaybeUninit<T>) -> Result<(), E>,
+    ) -> Result<Self, E> {
+        Self::try_emplace_raw_with((), |ptr| f(&mut *ptr.cast()))
+    }
 }
 
 /// A place containing multiple continuous values.
+///
+/// It is implemented for every [`Place<[T]>`](Place).
 pub trait SlicePlace<T> {
     /// # Safety
     /// `f` must initialize every `T` correctly.
     unsafe fn emplace_with(len: usize, f: impl FnOnce(&mut [MaybeUninit<T>])) -> Self;
 
+    /// The memory is filled with zero before `f` is called.
+    ///
     /// # Safety
     /// `f` must initialize every `T` correctly.
     unsafe fn emplace_zeroed_with(len: usize, f: impl FnOnce(&mut [MaybeUninit<T>])) -> Self;
+
+    /// If `f` returns an error, the memory is freed and the error is returned.
+    ///
+    /// # Errors
+    /// Returns the error of `f`.
+    ///
+    /// # Safety
+    /// `f` must initialize every `T` correctly if it returns `Ok`.
+    unsafe fn try_emplace_with<E>(
+        len: usize,
+        f: impl FnOnce(&mut [MaybeUninit<T>]) -> Result<(), E>,
+    ) -> Result<Self, E>
+    where
+        Self: Sized;
+
+    /// Creates `len` zeroed `T`.
+    #[must_use]
+    fn new_zeroed_slice_safe(len: usize) -> Self
+    where
+        Self: Sized,
+        T: Zeroable,
+    {
+        unsafe { Self::emplace_zeroed_with(len, |_| {}) }
+    }
+
+    /// Creates `len` `T` by [`DefaultInPlace`].
+    ///
+    /// If `T::default_in_place` panics, every `T` initialized before will be dropped.
+    #[must_use]
+    fn emplace_default(len: usize) -> Self
+    where
+        Self: Sized,
+        T: DefaultInPlace,
+    {
+        unsafe {
+            Self::emplace_with(len, |slice| {
+                crate::init_slice_with(slice, |_, this| T::default_in_place(this));
+            })
+        }
+    }
+
+    /// Creates a copy of `src` by [`CloneInto`].
+    ///
+    /// If `T::clone_into_uninit` panics, every `T` cloned before will be dropped.
+    #[must_use]
+    fn emplace_clone_slice(src: &[T]) -> Self
+    where
+        Self: Sized,
+        T: CloneInto,
+    {
+        unsafe {
+            Self::emplace_with(src.len(), |slice| {
+                crate::init_slice_with(slice, |idx, this| {
+                    src.get_unchecked(idx).clone_into_uninit(this);
+                });
+            })
+        }
+    }
+
+    /// Initializes every `T` in parallel by [`par_init_slice_with`](crate::par_init_slice_with).
+    ///
+    /// # Safety
+    /// `f` must initialize `T` correctly every time.
+    #[cfg(feature = "std")]
+    unsafe fn par_emplace_with(
+        len: usize,
+        threads: usize,
+        f: impl Fn(usize, &mut MaybeUninit<T>) + Sync,
+    ) -> Self
+    where
+        Self: Sized,
+        T: Send,
+    {
+        Self::emplace_with(len, |slice| {
+            crate::par_init_slice_with(slice, threads, f);
+        })
+    }
+}
+
+impl<T, P: Place<[T]>> SlicePlace<T> for P {
+    #[inline]
+    unsafe fn emplace_with(len: usize, f: impl FnOnce(&mut [MaybeUninit<T>])) -> Self {
+        Self::emplace_raw_with(len, |ptr| f(&mut *(ptr as *mut [MaybeUninit<T>])))
+    }
+
+    #[inline]
+    unsafe fn emplace_zeroed_with(len: usize, f: impl FnOnce(&mut [MaybeUninit<T>])) -> Self {
+        Self::emplace_zeroed_raw_with(len, |ptr| f(&mut *(ptr as *mut [MaybeUninit<T>])))
+    }
+
+    #[inline]
+    unsafe fn try_emplace_with<E>(
+        len: usize,
+        f: impl FnOnce(&mut [MaybeUninit<T>]) -> Result<(), E>,
+    ) -> Result<Self, E> {
+        Self::try_emplace_raw_with(len, |ptr| f(&mut *(ptr as *mut [MaybeUninit<T>])))
+    }
 }
+
+/// A place containing a struct whose last field is a slice.
+///
+/// It is not implemented for `Rc` and `Arc`, see [`Place`].
+pub trait DstPlace<T: ?Sized + UninitProjectDst>: Place<T> {
+    /// Creates a value whose trailing slice has `len` elements.
+    ///
+    /// `f` receives the uninitialized fields and the uninitialized trailing slice.
+    ///
+    /// # Safety
+    /// `f` must initialize every field and every element correctly.
+    unsafe fn emplace_dst_with(
+        len: usize,
+        f: impl FnOnce(T::Fields<'_>, &mut [MaybeUninit<T::Elem>]),
+    ) -> Self {
+        Self::emplace_raw_with(len, |ptr| {
+            let (fields, tail) = T::uninit_project_dst(ptr);
+            f(fields, tail);
+        })
+    }
+
+    /// The memory is filled with zero before `f` is called.
+    ///
+    /// # Safety
+    /// `f` must initialize every field and every element correctly.
+    unsafe fn emplace_dst_zeroed_with(
+        len: usize,
+        f: impl FnOnce(T::Fields<'_>, &mut [MaybeUninit<T::Elem>]),
+    ) -> Self {
+        Self::emplace_zeroed_raw_with(len, |ptr| {
+            let (fields, tail) = T::uninit_project_dst(ptr);
+            f(fields, tail);
+        })
+    }
+}
+
+impl<T: ?Sized + UninitProjectDst, P: Place<T>> DstPlace<T> for P {}
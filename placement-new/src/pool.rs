@@ -0,0 +1,207 @@
+use crate::guard::guard_on_unwind;
+use crate::{memset_zeroed, SinglePlace};
+
+use core::cell::RefCell;
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem::{self, MaybeUninit};
+use core::ops::{Deref, DerefMut};
+use core::ptr::{self, NonNull};
+
+use rust_alloc::boxed::Box;
+use rust_alloc::vec::Vec;
+
+/// A pool of heap slots for values of the same type.
+///
+/// Dropping a [`PoolBox`] runs the destructor of its value and returns the slot to the pool,
+/// so that the next emplacement reuses it instead of calling the allocator.
+pub struct Pool<T> {
+    free: RefCell<Vec<Box<MaybeUninit<T>>>>,
+    zero_on_return: bool,
+}
+
+/// A value emplaced in a [`Pool`].
+pub struct PoolBox<'a, T> {
+    ptr: NonNull<T>,
+    pool: &'a Pool<T>,
+    _marker: PhantomData<T>,
+}
+
+impl<T> Pool<T> {
+    /// Creates an empty pool.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            free: RefCell::new(Vec::new()),
+            zero_on_return: false,
+        }
+    }
+
+    /// Creates an empty pool which fills every returned slot with zero.
+    ///
+    /// Free slots of this pool are always zeroed,
+    /// so [`Pool::emplace_zeroed_with`] never has to clear a recycled slot.
+    #[must_use]
+    pub const fn new_zeroed() -> Self {
+        Self {
+            free: RefCell::new(Vec::new()),
+            zero_on_return: true,
+        }
+    }
+
+    /// Returns `true` if the pool fills every returned slot with zero.
+    #[must_use]
+    pub const fn zero_on_return(&self) -> bool {
+        self.zero_on_return
+    }
+
+    /// Returns the number of free slots.
+    #[must_use]
+    pub fn free_slots(&self) -> usize {
+        self.free.borrow().len()
+    }
+
+    /// Allocates `additional` free slots.
+    pub fn reserve(&self, additional: usize) {
+        let mut free = self.free.borrow_mut();
+        free.reserve(additional);
+        for _ in 0..additional {
+            free.push(Self::alloc_slot(self.zero_on_return));
+        }
+    }
+
+    /// Emplaces a value in a free slot, allocating a new slot if there is none.
+    ///
+    /// If `f` panics, the slot is returned to the pool.
+    ///
+    /// # Safety
+    /// `f` must initialize `T` correctly.
+    pub unsafe fn emplace_with(&self, f: impl FnOnce(&mut MaybeUninit<T>)) -> PoolBox<'_, T> {
+        self.emplace(false, f)
+    }
+
+    /// Emplaces a value in a zeroed free slot, allocating a new slot if there is none.
+    ///
+    /// If `f` panics, the slot is returned to the pool.
+    ///
+    /// # Safety
+    /// `f` must initialize `T` correctly.
+    pub unsafe fn emplace_zeroed_with(
+        &self,
+        f: impl FnOnce(&mut MaybeUninit<T>),
+    ) -> PoolBox<'_, T> {
+        self.emplace(true, f)
+    }
+
+    unsafe fn emplace(&self, zeroed: bool, f: impl FnOnce(&mut MaybeUninit<T>)) -> PoolBox<'_, T> {
+        let slot = self.take_slot(zeroed);
+        let ptr = Box::into_raw(slot);
+
+        guard_on_unwind(|| f(&mut *ptr), || self.put_slot(Box::from_raw(ptr)));
+
+        PoolBox {
+            ptr: NonNull::new_unchecked(ptr.cast()),
+            pool: self,
+            _marker: PhantomData,
+        }
+    }
+
+    #[allow(clippy::unnecessary_box_returns)]
+    fn alloc_slot(zeroed: bool) -> Box<MaybeUninit<T>> {
+        // `MaybeUninit<T>` needs no initialization
+        unsafe {
+            if zeroed {
+                Box::emplace_zeroed_with(|_| {})
+            } else {
+                Box::emplace_with(|_| {})
+            }
+        }
+    }
+
+    #[allow(clippy::unnecessary_box_returns)]
+    fn take_slot(&self, zeroed: bool) -> Box<MaybeUninit<T>> {
+        let slot = self.free.borrow_mut().pop();
+        if let Some(mut slot) = slot {
+            if zeroed && !self.zero_on_return {
+                memset_zeroed(&mut *slot);
+            }
+            return slot;
+        }
+        Self::alloc_slot(zeroed || self.zero_on_return)
+    }
+
+    fn put_slot(&self, mut slot: Box<MaybeUninit<T>>) {
+        if self.zero_on_return {
+            memset_zeroed(&mut *slot);
+        }
+        self.free.borrow_mut().push(slot);
+    }
+}
+
+impl<T> Default for Pool<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for Pool<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pool")
+            .field("free_slots", &self.free_slots())
+            .field("zero_on_return", &self.zero_on_return)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T> PoolBox<'a, T> {
+    /// Returns the pool which owns the slot.
+    #[must_use]
+    pub const fn pool(this: &Self) -> &'a Pool<T> {
+        this.pool
+    }
+
+    /// Moves the value out and returns the slot to the pool.
+    #[must_use]
+    pub fn into_inner(this: Self) -> T {
+        let this = mem::ManuallyDrop::new(this);
+        unsafe {
+            let value = ptr::read(this.ptr.as_ptr());
+            this.pool.put_slot(Box::from_raw(this.ptr.as_ptr().cast()));
+            value
+        }
+    }
+}
+
+impl<T> Drop for PoolBox<'_, T> {
+    fn drop(&mut self) {
+        let ptr = self.ptr.as_ptr();
+        let pool = self.pool;
+        unsafe {
+            guard_on_unwind(
+                || ptr::drop_in_place(ptr),
+                || pool.put_slot(Box::from_raw(ptr.cast())),
+            );
+            pool.put_slot(Box::from_raw(ptr.cast()));
+        }
+    }
+}
+
+impl<T> Deref for PoolBox<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T> DerefMut for PoolBox<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { self.ptr.as_mut() }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for PoolBox<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
@@ -0,0 +1,70 @@
+use core::alloc::Layout;
+use core::mem::MaybeUninit;
+use core::ptr;
+
+use rust_alloc::boxed::Box;
+
+/// Reuses the allocation of a [`Box`] for another type with the same layout.
+pub trait RecycleBox<T> {
+    /// Drops the old value and initializes a value of `U` in the same allocation.
+    ///
+    /// If dropping the old value or `f` panics, the allocation is freed.
+    ///
+    /// # Panics
+    /// Panics if the layouts of `T` and `U` are not the same.
+    ///
+    /// # Safety
+    /// `f` must initialize `U` correctly.
+    unsafe fn recycle_into<U>(self, f: impl FnOnce(&mut MaybeUninit<U>)) -> Box<U>;
+}
+
+/// Reuses the allocation of a boxed slice for another element type with the same layout.
+pub trait RecycleBoxedSlice<T> {
+    /// Drops the old elements and initializes the same number of `U` in the same allocation.
+    ///
+    /// If dropping the old elements or `f` panics, the allocation is freed.
+    ///
+    /// # Panics
+    /// Panics if the layouts of `T` and `U` are not the same.
+    ///
+    /// # Safety
+    /// `f` must initialize every `U` correctly.
+    unsafe fn recycle_into<U>(self, f: impl FnOnce(&mut [MaybeUninit<U>])) -> Box<[U]>;
+}
+
+#[inline]
+fn assert_same_layout<T, U>() {
+    assert!(
+        Layout::new::<T>() == Layout::new::<U>(),
+        "the layouts of the old type and the new type are not the same"
+    );
+}
+
+impl<T> RecycleBox<T> for Box<T> {
+    unsafe fn recycle_into<U>(self, f: impl FnOnce(&mut MaybeUninit<U>)) -> Box<U> {
+        assert_same_layout::<T, U>();
+
+        let mut new: Box<MaybeUninit<U>> = Box::from_raw(Self::into_raw(self).cast());
+
+        // the allocation is freed by `new` if dropping the old value or `f` panics
+        ptr::drop_in_place(new.as_mut_ptr().cast::<T>());
+        f(&mut new);
+
+        Box::from_raw(Box::into_raw(new).cast())
+    }
+}
+
+impl<T> RecycleBoxedSlice<T> for Box<[T]> {
+    unsafe fn recycle_into<U>(self, f: impl FnOnce(&mut [MaybeUninit<U>])) -> Box<[U]> {
+        assert_same_layout::<T, U>();
+
+        let mut new: Box<[MaybeUninit<U>]> =
+            Box::from_raw(Self::into_raw(self) as *mut [MaybeUninit<U>]);
+
+        // the allocation is freed by `new` if dropping the old elements or `f` panics
+        ptr::drop_in_place(ptr::addr_of_mut!(*new) as *mut [T]);
+        f(&mut new);
+
+        Box::from_raw(Box::into_raw(new) as *mut [U])
+    }
+}
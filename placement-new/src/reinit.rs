@@ -0,0 +1,48 @@
+use crate::guard::{abort_on_unwind, guard_on_unwind};
+
+use core::mem::MaybeUninit;
+use core::ptr;
+
+/// Reinitializes a value in place.
+///
+/// The old value is dropped and then `f` initializes `this` again.
+///
+/// If dropping the old value or `f` panics, the process is aborted,
+/// so that `this` is never observed uninitialized.
+///
+/// # Safety
+/// + `f` must initialize `T` correctly.
+#[inline]
+pub unsafe fn reinit_with<T>(this: &mut T, f: impl FnOnce(&mut MaybeUninit<T>)) {
+    let this: *mut T = this;
+    abort_on_unwind(|| {
+        ptr::drop_in_place(this);
+        f(&mut *this.cast());
+    });
+}
+
+/// Reinitializes a value in place.
+///
+/// The old value is dropped and then `f` initializes `this` again.
+///
+/// If dropping the old value or `f` panics, `this` is overwritten by the value returned by `fallback`
+/// and the panic continues. If `fallback` panics too, the process is aborted.
+/// Anything partially initialized by `f` is leaked.
+///
+/// # Safety
+/// + `f` must initialize `T` correctly.
+#[inline]
+pub unsafe fn reinit_with_or<T>(
+    this: &mut T,
+    f: impl FnOnce(&mut MaybeUninit<T>),
+    fallback: impl FnOnce() -> T,
+) {
+    let this: *mut T = this;
+    guard_on_unwind(
+        || {
+            ptr::drop_in_place(this);
+            f(&mut *this.cast());
+        },
+        || this.write(fallback()),
+    );
+}
@@ -31,7 +31,7 @@ pub unsafe fn init_slice_with<T>(
         },
         || {
             if mem::needs_drop::<T>() {
-                let partial_slice = (*slice).get_unchecked_mut(..*count);
+                let partial_slice = (&mut *slice).get_unchecked_mut(..*count);
                 for this in partial_slice {
                     ptr::drop_in_place(this.as_mut_ptr());
                 }
@@ -0,0 +1,140 @@
+use crate::Place;
+
+use core::fmt;
+use core::mem::MaybeUninit;
+use core::str::{self, Utf8Error};
+
+/// An error which occurs when a C string contains a nul byte before its end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InteriorNulError {
+    position: usize,
+}
+
+impl InteriorNulError {
+    /// Returns the position of the first nul byte.
+    #[must_use]
+    pub const fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl fmt::Display for InteriorNulError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "interior nul byte at position {}", self.position)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InteriorNulError {}
+
+/// Casts the pointer to an uninitialized string to its bytes.
+#[allow(clippy::missing_const_for_fn)] // MSRV
+unsafe fn uninit_bytes<'a, T: ?Sized>(ptr: *mut T, len: usize) -> &'a mut [MaybeUninit<u8>] {
+    core::slice::from_raw_parts_mut(ptr.cast(), len)
+}
+
+/// A place containing a string, which is written directly into the place.
+pub trait StrPlace: Place<str> {
+    /// Writes `len` bytes by `f`, and checks that they are valid UTF-8.
+    ///
+    /// The bytes are filled with zero before `f` is called.
+    /// If the bytes are invalid, the memory is freed and the error is returned.
+    ///
+    /// # Errors
+    /// Returns an error if the bytes are not valid UTF-8.
+    fn emplace_str_with(len: usize, f: impl FnOnce(&mut [u8])) -> Result<Self, Utf8Error> {
+        unsafe {
+            Self::try_emplace_zeroed_raw_with(len, |ptr| {
+                let bytes = core::slice::from_raw_parts_mut(ptr.cast::<u8>(), len);
+                f(bytes);
+                str::from_utf8(bytes).map(|_| ())
+            })
+        }
+    }
+
+    /// Writes `len` bytes by `f` without any check.
+    ///
+    /// # Safety
+    /// `f` must initialize every byte, and the bytes must be valid UTF-8.
+    unsafe fn emplace_str_unchecked_with(
+        len: usize,
+        f: impl FnOnce(&mut [MaybeUninit<u8>]),
+    ) -> Self {
+        Self::emplace_raw_with(len, |ptr| f(uninit_bytes(ptr, len)))
+    }
+}
+
+impl<P: Place<str>> StrPlace for P {}
+
+/// A place containing a C string, which is written directly into the place.
+///
+/// It is implemented for `Box<CStr>`, whose bytes are converted without copying.
+pub trait CStrPlace: Sized {
+    /// Writes `len` bytes by `f`, and appends a nul terminator.
+    ///
+    /// The bytes are filled with zero before `f` is called.
+    /// If a nul byte is written, the memory is freed and the error is returned.
+    ///
+    /// # Errors
+    /// Returns an error if the bytes contain a nul byte.
+    ///
+    /// # Panics
+    /// Panics if `len` is `usize::MAX`.
+    fn emplace_cstr_with(len: usize, f: impl FnOnce(&mut [u8])) -> Result<Self, InteriorNulError>;
+
+    /// Writes `len` bytes by `f` without any check, and appends a nul terminator.
+    ///
+    /// # Safety
+    /// `f` must initialize every byte, and the bytes must not contain a nul byte.
+    ///
+    /// # Panics
+    /// Panics if `len` is `usize::MAX`.
+    unsafe fn emplace_cstr_unchecked_with(
+        len: usize,
+        f: impl FnOnce(&mut [MaybeUninit<u8>]),
+    ) -> Self;
+}
+
+#[cfg(feature = "alloc")]
+mod alloc_impls {
+    use super::{CStrPlace, InteriorNulError};
+    use crate::{overwrite, SlicePlace};
+
+    use core::ffi::CStr;
+    use core::mem::MaybeUninit;
+
+    use rust_alloc::boxed::Box;
+    use rust_alloc::ffi::CString;
+
+    // `CString` takes the bytes with the nul terminator, and its capacity is exact
+    unsafe fn into_boxed_c_str(bytes: Box<[u8]>) -> Box<CStr> {
+        CString::from_vec_with_nul_unchecked(bytes.into_vec()).into_boxed_c_str()
+    }
+
+    impl CStrPlace for Box<CStr> {
+        fn emplace_cstr_with(
+            len: usize,
+            f: impl FnOnce(&mut [u8]),
+        ) -> Result<Self, InteriorNulError> {
+            let meta = len.checked_add(1).expect("length overflow");
+            let mut bytes = Box::<[u8]>::new_zeroed_slice_safe(meta);
+            f(&mut bytes[..len]);
+            if let Some(position) = bytes[..len].iter().position(|&b| b == 0) {
+                return Err(InteriorNulError { position });
+            }
+            Ok(unsafe { into_boxed_c_str(bytes) })
+        }
+
+        unsafe fn emplace_cstr_unchecked_with(
+            len: usize,
+            f: impl FnOnce(&mut [MaybeUninit<u8>]),
+        ) -> Self {
+            let meta = len.checked_add(1).expect("length overflow");
+            let bytes = Box::<[u8]>::emplace_with(meta, |buf| {
+                f(&mut buf[..len]);
+                overwrite(&mut buf[len], 0);
+            });
+            into_boxed_c_str(bytes)
+        }
+    }
+}
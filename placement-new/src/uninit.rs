@@ -1,4 +1,10 @@
-use core::mem::MaybeUninit;
+use core::cell::{Cell, UnsafeCell};
+use core::mem::{self, ManuallyDrop, MaybeUninit};
+use core::num::Wrapping;
+use core::ptr;
+
+use crate::__private::{UninitField, UninitProjectStep};
+use crate::PlaceTarget;
 
 /// A type with determined layout.
 /// It can be projected to another type with the same fields,
@@ -9,15 +15,57 @@ pub unsafe trait UninitProject<U>: Sized {
 }
 
 /// Projects a type to its uninitialized mirror.
+///
+/// `uninit_project!(this => .a.b)` projects through the fields `a` and `b` in one step,
+/// and returns `&mut MaybeUninit<_>` of the last field.
+///
+/// `uninit_project!(this => union Ty => field)` projects a union to one of its fields.
+/// Only one field of a union may be initialized.
 #[cfg(feature = "derive")]
 #[macro_export]
 macro_rules! uninit_project {
+    (@deep $mirror:expr => .$field:tt) => {
+        $crate::__private::UninitField::as_uninit(&mut $mirror.$field)
+    };
+    (@deep $mirror:expr => .$field:tt $(.$rest:tt)+) => {
+        $crate::uninit_project!(@deep
+            $crate::__private::UninitProjectStep::<_>::project_step(&mut $mirror.$field)
+            => $(.$rest)+
+        )
+    };
     ($this:expr) => {{
         $crate::UninitProject::<_>::uninit_project($this)
     }};
     ($this:expr => enum $ty:path => $variant:ident) => {{
         $crate::__private::__uninit_project_variant!($ty => $variant)($this)
     }};
+    ($this:expr => union $ty:path => $field:ident) => {{
+        $crate::__private::__uninit_project_union!($ty => $field)($this)
+    }};
+    ($this:expr => $(.$field:tt)+) => {{
+        $crate::uninit_project!(@deep $crate::UninitProject::<_>::uninit_project($this) => $(.$field)+)
+    }};
+}
+
+/// A type with a single uninitialized mirror.
+///
+/// It is implemented by the structs which derive [`UninitProject`], by arrays, by tuples,
+/// and by transparent wrappers, whose mirror is the uninitialized inner value.
+pub trait UninitProjectExt: Sized {
+    /// The uninitialized mirror.
+    type Uninit;
+
+    /// Projects a type to its uninitialized mirror.
+    fn uninit_project_ext(this: &mut MaybeUninit<Self>) -> &mut Self::Uninit
+    where
+        Self: UninitProject<Self::Uninit>,
+    {
+        UninitProject::uninit_project(this)
+    }
+}
+
+impl<T, const N: usize> UninitProjectExt for [T; N] {
+    type Uninit = [MaybeUninit<T>; N];
 }
 
 unsafe impl<T, const N: usize> UninitProject<[MaybeUninit<T>; N]> for [T; N] {
@@ -26,6 +74,135 @@ unsafe impl<T, const N: usize> UninitProject<[MaybeUninit<T>; N]> for [T; N] {
     }
 }
 
+macro_rules! impl_uninit_project_for_wrapper {
+    ($($ty:ident),+) => {
+        $(
+            impl<T> UninitProjectExt for $ty<T> {
+                type Uninit = MaybeUninit<T>;
+            }
+
+            unsafe impl<T> UninitProject<MaybeUninit<T>> for $ty<T> {
+                #[inline]
+                fn uninit_project(this: &mut MaybeUninit<Self>) -> &mut MaybeUninit<T> {
+                    // `$ty<T>` has the same layout as `T`
+                    unsafe { &mut *this.as_mut_ptr().cast() }
+                }
+            }
+        )+
+    };
+}
+
+impl_uninit_project_for_wrapper!(ManuallyDrop, Cell, UnsafeCell, Wrapping);
+
+/// A tuple which can be projected to its uninitialized elements.
+///
+/// A tuple is also projected by [`UninitProject`] to a tuple of `MaybeUninit` elements,
+/// so `uninit_project!(this => .a.0)` and `#[uninit_project(nested)]` reach its elements.
+///
+/// # Safety
+/// Every reference in `Fields` must point to the corresponding element of the tuple.
+pub unsafe trait UninitProjectTuple<'a>: Sized {
+    /// The references to every uninitialized element.
+    type Fields;
+
+    /// Projects a tuple to its uninitialized elements.
+    fn uninit_project_tuple(this: &'a mut MaybeUninit<Self>) -> Self::Fields;
+}
+
+// the layout of a tuple is not guaranteed to be the same as a tuple of `MaybeUninit`,
+// so it is checked after monomorphization
+macro_rules! tuple_layout_checks {
+    ($origin:ty, $mirror:ty, $($idx:tt),+) => {
+        const {
+            assert!(mem::size_of::<$origin>() == mem::size_of::<$mirror>());
+            assert!(mem::align_of::<$origin>() == mem::align_of::<$mirror>());
+            $(assert!(mem::offset_of!($origin, $idx) == mem::offset_of!($mirror, $idx));)+
+        }
+    };
+}
+
+macro_rules! impl_uninit_project_tuple {
+    ($(($($ty:ident: $idx:tt),+))+) => {
+        $(
+            unsafe impl<'a, $($ty: 'a),+> UninitProjectTuple<'a> for ($($ty,)+) {
+                type Fields = ($(&'a mut MaybeUninit<$ty>,)+);
+
+                #[inline]
+                fn uninit_project_tuple(this: &'a mut MaybeUninit<Self>) -> Self::Fields {
+                    let ptr = this.as_mut_ptr();
+                    // the elements are disjoint
+                    unsafe { ($(&mut *ptr::addr_of_mut!((*ptr).$idx).cast::<MaybeUninit<$ty>>(),)+) }
+                }
+            }
+
+            impl<$($ty),+> UninitProjectExt for ($($ty,)+) {
+                type Uninit = ($(MaybeUninit<$ty>,)+);
+            }
+
+            unsafe impl<$($ty),+> UninitProject<($(MaybeUninit<$ty>,)+)> for ($($ty,)+) {
+                #[inline]
+                fn uninit_project(this: &mut MaybeUninit<Self>) -> &mut ($(MaybeUninit<$ty>,)+) {
+                    tuple_layout_checks!(Self, ($(MaybeUninit<$ty>,)+), $($idx),+);
+                    unsafe { &mut *this.as_mut_ptr().cast() }
+                }
+            }
+
+            unsafe impl<$($ty),+> UninitField<($($ty,)+)> for ($(MaybeUninit<$ty>,)+) {
+                #[inline]
+                fn as_uninit(&mut self) -> &mut MaybeUninit<($($ty,)+)> {
+                    tuple_layout_checks!(($($ty,)+), Self, $($idx),+);
+                    unsafe { &mut *ptr::from_mut(self).cast() }
+                }
+            }
+
+            impl<$($ty),+> UninitProjectStep<Self> for ($(MaybeUninit<$ty>,)+) {
+                #[inline]
+                fn project_step(&mut self) -> &mut Self {
+                    self
+                }
+            }
+        )+
+    };
+}
+
+impl_uninit_project_tuple! {
+    (A: 0)
+    (A: 0, B: 1)
+    (A: 0, B: 1, C: 2)
+    (A: 0, B: 1, C: 2, D: 3)
+    (A: 0, B: 1, C: 2, D: 3, E: 4)
+    (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5)
+    (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6)
+    (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7)
+    (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8)
+    (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8, J: 9)
+    (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8, J: 9, K: 10)
+    (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8, J: 9, K: 10, L: 11)
+}
+
+/// A struct whose last field is a slice, which can be projected to its uninitialized fields.
+///
+/// It is implemented by the `repr(C)` structs with a trailing `[T]` field,
+/// which derive [`UninitProject`].
+///
+/// # Safety
+/// `uninit_project_dst` must return references to the corresponding fields.
+pub unsafe trait UninitProjectDst: PlaceTarget<Metadata = usize> {
+    /// The references to every field before the trailing slice.
+    type Fields<'a>;
+
+    /// The element of the trailing slice.
+    type Elem;
+
+    /// Projects a pointer to the uninitialized value to its fields and its trailing slice.
+    ///
+    /// # Safety
+    /// `ptr` must be valid for writes and must not be accessed through other pointers during `'a`.
+    unsafe fn uninit_project_dst<'a>(
+        ptr: *mut Self,
+    ) -> (Self::Fields<'a>, &'a mut [MaybeUninit<Self::Elem>]);
+}
+
 /// Sets the content of `T` to zero.
 #[inline]
 pub fn memset_zeroed<T>(this: &mut MaybeUninit<T>) {
@@ -0,0 +1,110 @@
+use core::marker::PhantomData;
+use core::mem::{self, MaybeUninit};
+use core::ptr;
+
+/// A pointer to an uninitialized `T`, which may be unaligned.
+///
+/// It never creates a reference to `T`,
+/// so it can initialize the fields of `repr(packed)` structs in place.
+#[derive(Debug)]
+pub struct UninitPtr<'a, T> {
+    ptr: *mut T,
+    _marker: PhantomData<&'a mut MaybeUninit<T>>,
+}
+
+impl<'a, T> UninitPtr<'a, T> {
+    /// Creates a pointer to `this`.
+    #[inline]
+    #[allow(clippy::missing_const_for_fn)] // MSRV
+    pub fn new(this: &'a mut MaybeUninit<T>) -> Self {
+        Self {
+            ptr: this.as_mut_ptr(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Creates a pointer from a raw pointer.
+    ///
+    /// # Safety
+    /// `ptr` must be valid for writes of `size_of::<T>()` bytes for `'a`,
+    /// and it must not be accessed through other pointers during `'a`.
+    /// `ptr` may be unaligned.
+    #[inline]
+    #[allow(clippy::missing_const_for_fn)] // MSRV
+    pub unsafe fn from_raw(ptr: *mut T) -> Self {
+        Self {
+            ptr,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the raw pointer, which may be unaligned.
+    #[inline]
+    #[must_use]
+    pub const fn as_ptr(&self) -> *mut T {
+        self.ptr
+    }
+
+    /// Reborrows the pointer for a shorter lifetime.
+    #[inline]
+    #[allow(clippy::missing_const_for_fn)] // MSRV
+    pub fn reborrow(&mut self) -> UninitPtr<'_, T> {
+        UninitPtr {
+            ptr: self.ptr,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Writes `value` without reading or dropping the old value.
+    #[inline]
+    #[allow(clippy::missing_const_for_fn)] // MSRV
+    pub fn write_unaligned(&mut self, value: T) {
+        unsafe { ptr::write_unaligned(self.ptr, value) }
+    }
+
+    /// Sets every byte of `T` to `byte`.
+    #[inline]
+    #[allow(clippy::missing_const_for_fn)] // MSRV
+    pub fn write_bytes(&mut self, byte: u8) {
+        unsafe { self.ptr.cast::<u8>().write_bytes(byte, mem::size_of::<T>()) }
+    }
+
+    /// Projects the pointer to the pointers of every field.
+    #[inline]
+    #[must_use]
+    pub fn project(self) -> T::Fields
+    where
+        T: UninitPtrProject<'a>,
+    {
+        T::project_fields(self)
+    }
+}
+
+impl<'a, T, const N: usize> UninitPtr<'a, [T; N]> {
+    /// Returns the pointer to the element at `idx`, or `None` if it is out of bounds.
+    #[inline]
+    #[must_use]
+    #[allow(clippy::missing_const_for_fn)] // MSRV
+    pub fn get(self, idx: usize) -> Option<UninitPtr<'a, T>> {
+        if idx < N {
+            Some(UninitPtr {
+                ptr: unsafe { self.ptr.cast::<T>().add(idx) },
+                _marker: PhantomData,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// A struct whose fields can be projected from an [`UninitPtr`].
+///
+/// # Safety
+/// Every pointer in `Fields` must point to the corresponding field of the struct.
+pub unsafe trait UninitPtrProject<'a>: Sized {
+    /// The pointers to every field.
+    type Fields;
+
+    /// Projects the pointer to the pointers of every field.
+    fn project_fields(this: UninitPtr<'a, Self>) -> Self::Fields;
+}
@@ -0,0 +1,59 @@
+use core::cell::{Cell, UnsafeCell};
+use core::marker::PhantomData;
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::num::{self, Wrapping};
+use core::ptr::NonNull;
+
+/// A type whose all-zero byte pattern is a valid value.
+///
+/// # Safety
+/// A value of `Self` filled with zero must be valid.
+pub unsafe trait Zeroable {}
+
+macro_rules! impl_zeroable {
+    ($($ty:ty),+) => {
+        $(
+            unsafe impl Zeroable for $ty {}
+        )+
+    };
+}
+
+impl_zeroable!(u8, u16, u32, u64, u128, usize);
+impl_zeroable!(i8, i16, i32, i64, i128, isize);
+impl_zeroable!(f32, f64, bool, char, ());
+
+impl_zeroable!(
+    Option<num::NonZeroU8>,
+    Option<num::NonZeroU16>,
+    Option<num::NonZeroU32>,
+    Option<num::NonZeroU64>,
+    Option<num::NonZeroU128>,
+    Option<num::NonZeroUsize>
+);
+impl_zeroable!(
+    Option<num::NonZeroI8>,
+    Option<num::NonZeroI16>,
+    Option<num::NonZeroI32>,
+    Option<num::NonZeroI64>,
+    Option<num::NonZeroI128>,
+    Option<num::NonZeroIsize>
+);
+
+unsafe impl<T> Zeroable for *const T {}
+unsafe impl<T> Zeroable for *mut T {}
+
+unsafe impl<T: ?Sized> Zeroable for Option<NonNull<T>> {}
+unsafe impl<T: ?Sized> Zeroable for Option<&T> {}
+unsafe impl<T: ?Sized> Zeroable for Option<&mut T> {}
+
+#[cfg(feature = "alloc")]
+unsafe impl<T: ?Sized> Zeroable for Option<rust_alloc::boxed::Box<T>> {}
+
+unsafe impl<T: Zeroable, const N: usize> Zeroable for [T; N] {}
+
+unsafe impl<T> Zeroable for MaybeUninit<T> {}
+unsafe impl<T: ?Sized> Zeroable for PhantomData<T> {}
+unsafe impl<T: Zeroable> Zeroable for ManuallyDrop<T> {}
+unsafe impl<T: Zeroable> Zeroable for Wrapping<T> {}
+unsafe impl<T: Zeroable> Zeroable for Cell<T> {}
+unsafe impl<T: Zeroable> Zeroable for UnsafeCell<T> {}